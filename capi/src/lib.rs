@@ -0,0 +1,308 @@
+//! C API for [`board_id`].
+//!
+//! This lives in its own crate because [`board_id`] forbids unsafe code, which an FFI layer
+//! can't do without.
+//!
+//! A [`BoardIdRaw`] is plain data the caller allocates. Alternatively, [`board_id_detect_parts`]
+//! allocates the ID itself and fills a [`BoardIdParts`] with its parts, which must be released with
+//! [`board_id_free`].
+//!
+//! Functions returning `int` return [`BOARD_ID_OK`] on success, or a negative error code:
+//! - [`BOARD_ID_ERR_NULL`]: a required pointer argument was NULL.
+//! - [`BOARD_ID_ERR_IO`]: detection failed with an I/O error.
+
+#![deny(missing_docs)]
+
+use std::{ffi::{c_char, c_int}, io::Write, ptr};
+
+use board_id::BoardId;
+
+/// Success.
+pub const BOARD_ID_OK: c_int = 0;
+/// A required pointer argument was NULL.
+pub const BOARD_ID_ERR_NULL: c_int = -1;
+/// Detection failed with an I/O error.
+pub const BOARD_ID_ERR_IO: c_int = -2;
+
+/// Motherboard ID, in a C-compatible layout.
+///
+/// The fields are private to the library, use the accessor functions.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct BoardIdRaw {
+    /// The parts, back to back.
+    buffer: [u8; 255],
+    /// The exclusive end for the vendor part.
+    vendor: u8,
+    /// The exclusive end for the name part.
+    name: u8,
+    /// The exclusive end for the version part.
+    version: u8,
+}
+
+impl From<&BoardId> for BoardIdRaw {
+    fn from(board: &BoardId) -> Self {
+        let mut raw = Self { buffer: [0; 255], vendor: 0, name: 0, version: 0 };
+        let mut end = 0;
+        for (part, field_end) in [(board.vendor(), &mut raw.vendor), (board.name(), &mut raw.name), (board.version(), &mut raw.version)] {
+            let part = part.unwrap_or_default();
+            raw.buffer[end..end + part.len()].copy_from_slice(part);
+            end += part.len();
+            *field_end = end as u8;
+        }
+        raw
+    }
+}
+
+impl BoardIdRaw {
+    /// Gets the part between the given ends.
+    fn part(&self, start: u8, end: u8) -> BoardIdPart {
+        if start < end {
+            BoardIdPart { ptr: self.buffer[start as usize..].as_ptr(), len: (end - start) as usize }
+        } else {
+            BoardIdPart::NONE
+        }
+    }
+
+    /// Converts back to a [`BoardId`].
+    fn board_id(&self) -> BoardId {
+        let part = |start: u8, end: u8| (start < end).then(|| &self.buffer[start as usize..end as usize]);
+        BoardId::from_parts(part(0, self.vendor), part(self.vendor, self.name), part(self.name, self.version))
+            .expect("the parts came from a BoardId so they fit")
+    }
+}
+
+/// A part of a [`BoardIdRaw`]: a pointer and a length, not NUL-terminated.
+///
+/// `ptr` is NULL if the part hasn't been detected.
+/// The pointer borrows from the [`BoardIdRaw`] and is only valid as long as it is.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoardIdPart {
+    /// The part's bytes, or NULL if undetected.
+    pub ptr: *const u8,
+    /// The part's length in bytes.
+    pub len: usize,
+}
+
+impl BoardIdPart {
+    /// An undetected part.
+    const NONE: Self = Self { ptr: ptr::null(), len: 0 };
+}
+
+/// The parts of a motherboard ID allocated by [`board_id_detect_parts`].
+///
+/// The parts borrow from the allocation, and are only valid until it's released by
+/// [`board_id_free`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct BoardIdParts {
+    /// The board's vendor / brand.
+    pub vendor: BoardIdPart,
+    /// The board's name / model.
+    pub name: BoardIdPart,
+    /// The board's version.
+    pub version: BoardIdPart,
+    /// The allocation, which is private to the library.
+    storage: *mut BoardIdRaw,
+}
+
+/// Detects the motherboard ID into `out`.
+///
+/// Returns [`BOARD_ID_OK`], [`BOARD_ID_ERR_NULL`] if `out` is NULL, or [`BOARD_ID_ERR_IO`].
+///
+/// # Safety
+/// `out` must be NULL or valid for writes of a [`BoardIdRaw`].
+#[no_mangle]
+pub unsafe extern "C" fn board_id_detect(out: *mut BoardIdRaw) -> c_int {
+    if out.is_null() { return BOARD_ID_ERR_NULL }
+    match BoardId::detect() {
+        Ok(board) => {
+            out.write(BoardIdRaw::from(&board));
+            BOARD_ID_OK
+        }
+        Err(_) => BOARD_ID_ERR_IO,
+    }
+}
+
+/// Detects the motherboard ID into a library allocation, and fills `out` with its parts.
+///
+/// On success, `out` must be released with [`board_id_free`]. On failure, `out` is left untouched.
+/// Returns [`BOARD_ID_OK`], [`BOARD_ID_ERR_NULL`] if `out` is NULL, or [`BOARD_ID_ERR_IO`].
+///
+/// # Safety
+/// `out` must be NULL or valid for writes of a [`BoardIdParts`].
+#[no_mangle]
+pub unsafe extern "C" fn board_id_detect_parts(out: *mut BoardIdParts) -> c_int {
+    if out.is_null() { return BOARD_ID_ERR_NULL }
+    match BoardId::detect() {
+        Ok(board) => {
+            out.write(BoardIdParts::new(BoardIdRaw::from(&board)));
+            BOARD_ID_OK
+        }
+        Err(_) => BOARD_ID_ERR_IO,
+    }
+}
+
+/// Releases the allocation of a [`BoardIdParts`] filled by [`board_id_detect_parts`], and resets
+/// its parts to undetected.
+///
+/// Does nothing if `parts` is NULL or was already released.
+///
+/// # Safety
+/// `parts` must be NULL or point to a [`BoardIdParts`] filled by [`board_id_detect_parts`].
+#[no_mangle]
+pub unsafe extern "C" fn board_id_free(parts: *mut BoardIdParts) {
+    let Some(parts) = parts.as_mut() else { return };
+    if !parts.storage.is_null() { drop(Box::from_raw(parts.storage)) }
+    *parts = BoardIdParts { vendor: BoardIdPart::NONE, name: BoardIdPart::NONE, version: BoardIdPart::NONE, storage: ptr::null_mut() };
+}
+
+impl BoardIdParts {
+    /// Moves the ID into an allocation, and borrows its parts.
+    fn new(raw: BoardIdRaw) -> Self {
+        let storage = Box::into_raw(Box::new(raw));
+        let raw = unsafe { &*storage };
+        Self {
+             vendor: raw.part(0, raw.vendor),
+               name: raw.part(raw.vendor, raw.name),
+            version: raw.part(raw.name, raw.version),
+            storage,
+        }
+    }
+}
+
+/// Gets the board's vendor / brand.
+///
+/// # Safety
+/// `id` must be NULL or point to a [`BoardIdRaw`] filled by the library.
+#[no_mangle]
+pub unsafe extern "C" fn board_id_vendor(id: *const BoardIdRaw) -> BoardIdPart {
+    id.as_ref().map_or(BoardIdPart::NONE, |id| id.part(0, id.vendor))
+}
+
+/// Gets the board's name / model.
+///
+/// # Safety
+/// `id` must be NULL or point to a [`BoardIdRaw`] filled by the library.
+#[no_mangle]
+pub unsafe extern "C" fn board_id_name(id: *const BoardIdRaw) -> BoardIdPart {
+    id.as_ref().map_or(BoardIdPart::NONE, |id| id.part(id.vendor, id.name))
+}
+
+/// Gets the board's version.
+///
+/// # Safety
+/// `id` must be NULL or point to a [`BoardIdRaw`] filled by the library.
+#[no_mangle]
+pub unsafe extern "C" fn board_id_version(id: *const BoardIdRaw) -> BoardIdPart {
+    id.as_ref().map_or(BoardIdPart::NONE, |id| id.part(id.name, id.version))
+}
+
+/// Formats the motherboard ID for display into `buf`, like `snprintf`.
+///
+/// Writes at most `len` bytes including the NUL terminator (nothing if `len` is 0), and returns
+/// the length of the full formatted string excluding the NUL terminator, so a return value of
+/// `len` or more means the output was truncated. Returns 0 if `id` is NULL.
+///
+/// # Safety
+/// `id` must be NULL or point to a [`BoardIdRaw`] filled by the library, and `buf` must be valid
+/// for writes of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn board_id_display(id: *const BoardIdRaw, buf: *mut c_char, len: usize) -> usize {
+    let Some(id) = id.as_ref() else { return 0 };
+    let mut formatted = Vec::new();
+    write!(formatted, "{}", id.board_id()).expect("writing to a Vec doesn't fail");
+    if !buf.is_null() && len > 0 {
+        let n = formatted.len().min(len - 1);
+        ptr::copy_nonoverlapping(formatted.as_ptr(), buf.cast::<u8>(), n);
+        buf.add(n).write(0);
+    }
+    formatted.len()
+}
+
+#[cfg(test)]
+mod test {
+    use std::{ffi::CStr, mem::MaybeUninit, slice};
+
+    use super::*;
+
+    fn raw(vendor: Option<&str>, name: Option<&str>, version: Option<&str>) -> BoardIdRaw {
+        BoardIdRaw::from(&BoardId::from_parts(vendor.map(str::as_bytes), name.map(str::as_bytes), version.map(str::as_bytes)).unwrap())
+    }
+
+    fn bytes(part: BoardIdPart) -> Option<&'static [u8]> {
+        (!part.ptr.is_null()).then(|| unsafe { slice::from_raw_parts(part.ptr, part.len) })
+    }
+
+    #[test]
+    fn detect() {
+        let mut out = MaybeUninit::<BoardIdRaw>::uninit();
+        assert_eq!(unsafe { board_id_detect(out.as_mut_ptr()) }, BOARD_ID_OK);
+        let detected = BoardIdRaw::from(&BoardId::detect().unwrap());
+        assert_eq!(unsafe { out.assume_init() }.board_id(), detected.board_id());
+    }
+
+    #[test]
+    fn detect_null() {
+        assert_eq!(unsafe { board_id_detect(ptr::null_mut()) }, BOARD_ID_ERR_NULL);
+    }
+
+    #[test]
+    fn detect_parts() {
+        let mut out = MaybeUninit::<BoardIdParts>::uninit();
+        assert_eq!(unsafe { board_id_detect_parts(out.as_mut_ptr()) }, BOARD_ID_OK);
+        let mut parts = unsafe { out.assume_init() };
+        let detected = BoardId::detect().unwrap();
+        assert_eq!(bytes(parts .vendor), detected.vendor ());
+        assert_eq!(bytes(parts   .name), detected.name   ());
+        assert_eq!(bytes(parts.version), detected.version());
+        unsafe { board_id_free(&mut parts) };
+        assert!(parts.storage.is_null());
+        unsafe { board_id_free(&mut parts) };
+        unsafe { board_id_free(ptr::null_mut()) };
+        assert_eq!(unsafe { board_id_detect_parts(ptr::null_mut()) }, BOARD_ID_ERR_NULL);
+    }
+
+    #[test]
+    fn parts() {
+        let mut parts = BoardIdParts::new(raw(Some("VENDOR"), Some("NAME"), None));
+        assert_eq!(bytes(parts .vendor), Some("VENDOR".as_bytes()));
+        assert_eq!(bytes(parts   .name), Some("NAME"  .as_bytes()));
+        assert_eq!(bytes(parts.version), None                     );
+        unsafe { board_id_free(&mut parts) };
+        assert_eq!([parts.vendor, parts.name, parts.version], [BoardIdPart::NONE; 3]);
+    }
+
+    #[test]
+    fn accessors() {
+        let id = Box::leak(Box::new(raw(Some("VENDOR"), None, Some("VERSION"))));
+        assert_eq!(bytes(unsafe { board_id_vendor (id) }), Some("VENDOR" .as_bytes()));
+        assert_eq!(bytes(unsafe { board_id_name   (id) }), None                     );
+        assert_eq!(bytes(unsafe { board_id_version(id) }), Some("VERSION".as_bytes()));
+    }
+
+    #[test]
+    fn accessors_null() {
+        assert_eq!(unsafe { board_id_vendor (ptr::null()) }, BoardIdPart::NONE);
+        assert_eq!(unsafe { board_id_name   (ptr::null()) }, BoardIdPart::NONE);
+        assert_eq!(unsafe { board_id_version(ptr::null()) }, BoardIdPart::NONE);
+    }
+
+    #[test]
+    fn display() {
+        let id = raw(Some("VENDOR"), Some("NAME"), Some("VERSION"));
+        let mut buf = [0x7f as c_char; 32];
+        assert_eq!(unsafe { board_id_display(&id, buf.as_mut_ptr(), buf.len()) }, 19);
+        assert_eq!(unsafe { CStr::from_ptr(buf.as_ptr()) }.to_bytes(), b"VENDOR NAME VERSION");
+    }
+
+    #[test]
+    fn display_truncated() {
+        let id = raw(Some("VENDOR"), Some("NAME"), Some("VERSION"));
+        let mut buf = [0x7f as c_char; 7];
+        assert_eq!(unsafe { board_id_display(&id, buf.as_mut_ptr(), buf.len()) }, 19);
+        assert_eq!(unsafe { CStr::from_ptr(buf.as_ptr()) }.to_bytes(), b"VENDOR");
+        assert_eq!(unsafe { board_id_display(&id, ptr::null_mut(), 0) }, 19);
+    }
+}
@@ -0,0 +1,43 @@
+//! Compares scanning many roots with [`BoardId::detect_at`] and with a [`ScanSession`].
+//!
+//! Run with `cargo run --release --example scan_bench [ROOTS] [ROUNDS]`.
+
+use std::{fs, time::Instant};
+
+use board_id::{BoardId, ScanSession};
+
+fn main() {
+    let mut args = std::env::args().skip(1).map(|arg| arg.parse::<usize>().expect("expected a number"));
+    let roots = args.next().unwrap_or(1000);
+    let rounds = args.next().unwrap_or(10);
+
+    let dir = std::env::temp_dir().join(format!("board_id-scan-bench-{}", std::process::id()));
+    let roots = (0..roots).map(|i| {
+        let root = dir.join(i.to_string());
+        let dmi = root.join("sys/class/dmi/id");
+        fs::create_dir_all(&dmi).unwrap();
+        if i % 2 == 0 {
+            fs::write(dmi.join("board_vendor"), "VENDOR\n").unwrap();
+            fs::write(dmi.join("board_name"), format!("NAME {i}\n")).unwrap();
+        }
+        root
+    }).collect::<Vec<_>>();
+
+    let start = Instant::now();
+    for _ in 0..rounds {
+        for root in &roots { BoardId::detect_at(root).unwrap(); }
+    }
+    let one_shot = start.elapsed();
+
+    let start = Instant::now();
+    let mut session = ScanSession::new();
+    for _ in 0..rounds {
+        for result in session.detect_many(roots.iter().map(|root| root.as_path())) { result.unwrap(); }
+    }
+    let session = start.elapsed();
+
+    fs::remove_dir_all(&dir).unwrap();
+    let detections = (roots.len() * rounds) as u32;
+    println!("detect_at:   {one_shot:?} ({:?} per root)", one_shot / detections);
+    println!("ScanSession: {session:?} ({:?} per root)", session / detections);
+}
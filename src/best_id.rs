@@ -0,0 +1,128 @@
+//! [`best_id`], the most specific identifier available, out of several sources.
+
+use std::path::Path;
+
+use crate::{devicetree, dmi, is_placeholder, looks_like_serial, open_existing_file, BoardId, DmiField, Part};
+
+/// Product UUIDs that firmware ships on every unit, so that identify nothing.
+const JUNK_UUIDS: &[&str] = &[
+    "00000000-0000-0000-0000-000000000000",
+    "ffffffff-ffff-ffff-ffff-ffffffffffff",
+    "03000200-0400-0500-0006-000700080009",
+    "00020003-0004-0005-0006-000700080009",
+];
+
+/// An identifier found by [`best_id`], tagged with what it is.
+// returned once per call, so the inline board isn't worth a box
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub enum BestId {
+    /// The `product_uuid` DMI attribute, lowercased.
+    Uuid(String),
+    /// The `board_serial` DMI attribute, trimmed.
+    Serial(Vec<u8>),
+    /// The `board_vendor` and `board_name` DMI attributes.
+    VendorModel(BoardId),
+    /// The first (most specific) entry of the device tree `compatible` property.
+    DeviceTree(Vec<u8>),
+}
+
+/// Gets the most specific identifier available.
+///
+/// See [`best_id_at`].
+pub fn best_id() -> Option<BestId> { best_id_at("/") }
+
+/// Gets the most specific identifier available on the system whose filesystem is rooted at the
+/// given path.
+///
+/// The sources are tried in order:
+/// 1. [`BestId::Uuid`], if it's a well-formed UUID and not one shipped on every unit (e.g. all
+///    zeros).
+/// 2. [`BestId::Serial`], if it [looks like a serial](looks_like_serial).
+/// 3. [`BestId::VendorModel`], if neither is a [placeholder](is_placeholder).
+/// 4. [`BestId::DeviceTree`], if it isn't empty.
+///
+/// Sources that are missing or unreadable (e.g. the root-only UUID and serial, for other users)
+/// are skipped. Returns `None` if none is usable.
+pub fn best_id_at(root: impl AsRef<Path>) -> Option<BestId> {
+    let root = root.as_ref();
+    let read = |field| dmi::read_attribute(root, field).ok().flatten();
+    if let Some(uuid) = read(DmiField::ProductUuid).and_then(|uuid| String::from_utf8(uuid).ok()) {
+        let uuid = uuid.trim().to_ascii_lowercase();
+        if is_uuid(&uuid) && !JUNK_UUIDS.contains(&uuid.as_str()) { return Some(BestId::Uuid(uuid)) }
+    }
+    if let Some(serial) = read(DmiField::BoardSerial).filter(|serial| looks_like_serial(serial)) {
+        return Some(BestId::Serial(serial.trim_ascii().to_vec()))
+    }
+    if let (Some(vendor), Some(name)) = (read(DmiField::BoardVendor), read(DmiField::BoardName)) {
+        if let Ok(board) = BoardId::from_parts(Some(&vendor), Some(&name), None) {
+            if board.part_canonical(Part::Vendor).is_some() && board.part_canonical(Part::Name).is_some() {
+                return Some(BestId::VendorModel(board))
+            }
+        }
+    }
+    let mut compatible = Vec::new();
+    for dir in devicetree::DIRS {
+        let Ok(file) = open_existing_file(root.join(dir).join("compatible")) else { continue };
+        if devicetree::read_property(file, &mut compatible).is_err() { continue }
+        let first = compatible.split(|&b| b == 0).next().unwrap_or_default().trim_ascii();
+        if !first.is_empty() && !is_placeholder(first) { return Some(BestId::DeviceTree(first.to_vec())) }
+    }
+    None
+}
+
+/// Checks whether the (lowercase) value is a UUID in its hyphenated form.
+fn is_uuid(value: &str) -> bool {
+    value.len() == 36 && value.bytes().enumerate().all(|(i, b)| match i {
+        8 | 13 | 18 | 23 => b == b'-',
+        _ => b.is_ascii_digit() || (b'a'..=b'f').contains(&b),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    #[test]
+    fn uuids() {
+        assert!( is_uuid("4c4c4544-0051-3510-8052-b4c04f4e3332"));
+        assert!(!is_uuid("4c4c4544-0051-3510-8052-b4c04f4e333"));
+        assert!(!is_uuid("4c4c4544005135108052b4c04f4e333200ab"));
+        assert!(!is_uuid("4c4c4544-0051-3510-8052-b4c04f4e333g"));
+    }
+
+    #[test]
+    fn order() {
+        let root = TempDir::new("best-id-order");
+        root.write("proc/device-tree/compatible", "raspberrypi,4-model-b\0brcm,bcm2711\0");
+        assert_eq!(best_id_at(root.path()), Some(BestId::DeviceTree(b"raspberrypi,4-model-b".to_vec())));
+        root.write("sys/class/dmi/id/board_vendor", "Dell Inc.\n");
+        root.write("sys/class/dmi/id/board_name", "0UW306\n");
+        let board = BoardId::from_parts(Some(b"Dell Inc."), Some(b"0UW306"), None).unwrap();
+        assert_eq!(best_id_at(root.path()), Some(BestId::VendorModel(board)));
+        root.write("sys/class/dmi/id/board_serial", "..CN697027AB0123.\n");
+        assert_eq!(best_id_at(root.path()), Some(BestId::Serial(b"..CN697027AB0123.".to_vec())));
+        root.write("sys/class/dmi/id/product_uuid", "4C4C4544-0051-3510-8052-B4C04F4E3332\n");
+        assert_eq!(best_id_at(root.path()), Some(BestId::Uuid("4c4c4544-0051-3510-8052-b4c04f4e3332".to_owned())));
+    }
+
+    #[test]
+    fn skips_junk() {
+        let root = TempDir::new("best-id-junk");
+        root.write("sys/class/dmi/id/product_uuid", "03000200-0400-0500-0006-000700080009\n");
+        root.write("sys/class/dmi/id/board_serial", "To be filled by O.E.M.\n");
+        root.write("sys/class/dmi/id/board_vendor", "ASUSTeK COMPUTER INC.\n");
+        root.write("sys/class/dmi/id/board_name", "Default string\n");
+        root.write("sys/firmware/devicetree/base/compatible", "\0");
+        assert_eq!(best_id_at(root.path()), None);
+        root.write("sys/class/dmi/id/board_name", "PRIME B550-PLUS\n");
+        let board = BoardId::from_parts(Some(b"ASUSTeK COMPUTER INC."), Some(b"PRIME B550-PLUS"), None).unwrap();
+        assert_eq!(best_id_at(root.path()), Some(BestId::VendorModel(board)));
+    }
+
+    #[test]
+    fn nothing() {
+        assert_eq!(best_id_at(TempDir::new("best-id-nothing").path()), None);
+    }
+}
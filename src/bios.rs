@@ -0,0 +1,85 @@
+//! The board's age, estimated from its BIOS date.
+
+use std::{io, path::Path, time::{Duration, SystemTime}};
+
+use crate::{dmi, BoardId, DmiField};
+
+/// Parses a DMI BIOS date, which has the `MM/DD/YYYY` format, into a time (at midnight UTC).
+fn parse_bios_date(date: &[u8]) -> Option<SystemTime> {
+    let date = std::str::from_utf8(date).ok()?;
+    let mut fields = date.split('/');
+    let [month, day, year] = [fields.next()?, fields.next()?, fields.next()?];
+    if fields.next().is_some() || year.len() != 4 { return None }
+    let parse = |field: &str| field.bytes().all(|b| b.is_ascii_digit()).then(|| field.parse::<u32>().ok()).flatten();
+    let (month, day, year) = (parse(month)?, parse(day)?, parse(year)?);
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let month_days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 => if leap { 29 } else { 28 },
+        _ => return None,
+    };
+    if year < 1970 || !(1..=month_days).contains(&day) { return None }
+
+    // Days since the epoch, per Howard Hinnant's `days_from_civil`.
+    let (year, month, day) = (year as u64, month as u64, day as u64);
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y / 400;
+    let yoe = y - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+    Some(SystemTime::UNIX_EPOCH + Duration::from_secs(days * 24 * 60 * 60))
+}
+
+impl BoardId {
+    /// Estimates the board's age from its BIOS date.
+    ///
+    /// Returns `Ok(None)` if the BIOS date is absent or malformed. A BIOS date in the future makes
+    /// for a zero age.
+    pub fn board_age() -> io::Result<Option<Duration>> { Self::board_age_at("/", SystemTime::now()) }
+
+    /// Estimates the age, as of `now`, of the board of the system whose filesystem is rooted at
+    /// the given path.
+    ///
+    /// See [`Self::board_age`].
+    pub fn board_age_at(root: impl AsRef<Path>, now: SystemTime) -> io::Result<Option<Duration>> {
+        let date = dmi::read_attribute(root.as_ref(), DmiField::BiosDate)?.as_deref().and_then(parse_bios_date);
+        Ok(date.map(|date| now.duration_since(date).unwrap_or(Duration::ZERO)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    #[test]
+    fn parse() {
+        assert_eq!(parse_bios_date(b"01/01/1970"), Some(SystemTime::UNIX_EPOCH));
+        assert_eq!(parse_bios_date(b"03/01/2000"), Some(SystemTime::UNIX_EPOCH + Duration::from_secs(11_017 * DAY)));
+        assert_eq!(parse_bios_date(b"02/29/2024"), Some(SystemTime::UNIX_EPOCH + Duration::from_secs(19_782 * DAY)));
+        for malformed in ["", "2024-02-29", "02/30/2024", "02/29/2023", "13/01/2024", "00/10/2024", "1/2/24", "01/02/2024/1", "+1/02/2024", "01/01/1969"] {
+            assert_eq!(parse_bios_date(malformed.as_bytes()), None, "{malformed}");
+        }
+    }
+
+    #[test]
+    fn age() {
+        let root = TempDir::new("bios-age");
+        root.write("sys/class/dmi/id/bios_date", "02/29/2024\n");
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(19_782 * DAY + 10 * DAY);
+        assert_eq!(BoardId::board_age_at(root.path(), now).unwrap(), Some(Duration::from_secs(10 * DAY)));
+        assert_eq!(BoardId::board_age_at(root.path(), SystemTime::UNIX_EPOCH).unwrap(), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn absent_or_malformed() {
+        let root = TempDir::new("bios-absent");
+        assert_eq!(BoardId::board_age_at(root.path(), SystemTime::now()).unwrap(), None);
+        root.write("sys/class/dmi/id/bios_date", "sometime\n");
+        assert_eq!(BoardId::board_age_at(root.path(), SystemTime::now()).unwrap(), None);
+    }
+}
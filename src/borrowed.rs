@@ -0,0 +1,192 @@
+//! [`BoardIdRef`], a borrowed [`BoardId`].
+
+use core::fmt::{self, Display};
+#[cfg(feature = "std")] use std::{io::{self, Read}, path::Path, sync::OnceLock};
+
+use crate::{fmt_parts, BoardId, CapacityError};
+#[cfg(feature = "std")] use crate::{dmi, open_existing_file, DmiField, NewlinePolicy};
+
+/// The error for a buffer too small for [`BoardId::detect_into`].
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub struct BufferTooSmall {
+    /// The buffer size the parts need.
+    pub needed: usize,
+}
+
+impl Display for BufferTooSmall {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the board ID needs a buffer of {} bytes", self.needed)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BufferTooSmall {}
+
+/// A borrowed motherboard ID: the parts back to back in borrowed storage.
+#[derive(Debug, Hash, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
+pub struct BoardIdRef<'a> {
+    /// The parts, back to back.
+    bytes: &'a [u8],
+    /// The exclusive end for the vendor part.
+    vendor: usize,
+    /// The exclusive end for the name part.
+    name: usize,
+}
+
+impl<'a> BoardIdRef<'a> {
+    /// Creates a [`BoardIdRef`] from the parts back to back, and the exclusive ends of the vendor
+    /// and name parts.
+    pub(crate) fn new(bytes: &'a [u8], vendor: usize, name: usize) -> Self { Self { bytes, vendor, name } }
+
+    /// Gets the board's vendor / brand.
+    #[inline]
+    pub fn vendor(&self) -> Option<&'a [u8]> {
+        (self.vendor > 0).then_some(&self.bytes[..self.vendor])
+    }
+
+    /// Gets the board's name / model.
+    #[inline]
+    pub fn name(&self) -> Option<&'a [u8]> {
+        (self.vendor != self.name).then_some(&self.bytes[self.vendor..self.name])
+    }
+
+    /// Gets the board's version.
+    #[inline]
+    pub fn version(&self) -> Option<&'a [u8]> {
+        (self.name < self.bytes.len()).then_some(&self.bytes[self.name..])
+    }
+
+    /// Copies the parts into an owned [`BoardId`].
+    ///
+    /// Fails if the parts don't fit in a [`BoardId`]'s buffer.
+    pub fn to_board_id(&self) -> Result<BoardId, CapacityError> {
+        BoardId::from_parts(self.vendor(), self.name(), self.version())
+    }
+}
+
+impl<'a> From<&'a BoardId> for BoardIdRef<'a> {
+    fn from(board: &'a BoardId) -> Self {
+        Self { bytes: board.as_ref(), vendor: board.vendor as usize, name: board.name as usize }
+    }
+}
+
+/// Same as [`BoardId`]'s [`Display`] implementation.
+impl Display for BoardIdRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_parts(f, self.vendor(), self.name(), self.version())
+    }
+}
+
+impl BoardId {
+    /// Detects the [`BoardId`] once per process, and gets a borrowed view of it that's valid for the
+    /// rest of the process.
+    ///
+    /// The detected ID is intentionally never freed, which suits globals and avoids re-detection.
+    /// It's the same process-global ID as [`Self::cached`]'s.
+    #[cfg(feature = "std")]
+    pub fn leaked() -> io::Result<&'static BoardIdRef<'static>> {
+        static VIEW: OnceLock<BoardIdRef<'static>> = OnceLock::new();
+        if let Some(view) = VIEW.get() { return Ok(view) }
+        let board = Self::cached()?;
+        Ok(VIEW.get_or_init(|| board.into()))
+    }
+
+    /// Detects the board's parts from DMI straight into the given buffer, and gets a borrowed view
+    /// of them, without making a [`BoardId`].
+    ///
+    /// The parts are stripped like [`Self::from_streams`]'s, but they're limited by the buffer
+    /// rather than a [`BoardId`]'s capacity. Fails with how many bytes are needed if they don't fit.
+    /// Unlike [`Self::detect`] there's no device tree fallback. See [`Self::detect_into_at`].
+    #[cfg(feature = "std")]
+    pub fn detect_into(buf: &mut [u8]) -> io::Result<Result<BoardIdRef<'_>, BufferTooSmall>> { Self::detect_into_at("/", buf) }
+
+    /// Detects the parts of the board of the system whose filesystem is rooted at the given path
+    /// from DMI straight into the given buffer, and gets a borrowed view of them.
+    ///
+    /// See [`Self::detect_into`].
+    #[cfg(feature = "std")]
+    pub fn detect_into_at(root: impl AsRef<Path>, buf: &mut [u8]) -> io::Result<Result<BoardIdRef<'_>, BufferTooSmall>> {
+        let dir = root.as_ref().join(dmi::DIR);
+        let mut ends = [0; 3];
+        let mut needed = 0;
+        for (end, field) in ends.iter_mut().zip([DmiField::BoardVendor, DmiField::BoardName, DmiField::BoardVersion]) {
+            if let Some(file) = open_existing_file(dir.join(field.attribute()))? {
+                needed += read_stripped(file, buf.get_mut(needed..).unwrap_or_default())?;
+            }
+            *end = needed;
+        }
+        if needed > buf.len() { return Ok(Err(BufferTooSmall { needed })) }
+        Ok(Ok(BoardIdRef::new(&buf[..needed], ends[0], ends[1])))
+    }
+}
+
+/// Reads the stream into the buffer as far as it fits, and gets the length of what it read once
+/// its terminator is stripped as per the default [`NewlinePolicy`], even if it didn't fit.
+#[cfg(feature = "std")]
+fn read_stripped(mut stream: impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let (mut n, mut scratch) = (0, [0u8; 64]);
+    // the last two bytes read, which are all the policy looks at
+    let mut tail = [0u8; 2];
+    loop {
+        let chunk = if n < buf.len() { &mut buf[n..] } else { &mut scratch[..] };
+        let m = stream.read(chunk)?;
+        if m == 0 { break }
+        let read = &chunk[..m];
+        tail = if m >= 2 { [read[m - 2], read[m - 1]] } else { [tail[1], read[0]] };
+        n += m;
+    }
+    let tail = &tail[2 - n.min(2)..];
+    Ok(n - (tail.len() - NewlinePolicy::default().trimmed_len(tail)))
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn accessors() {
+        let board = BoardId::from_parts(Some(b"VENDOR"), None, Some(b"VERSION")).unwrap();
+        let view = BoardIdRef::from(&board);
+        assert_eq!( view.vendor(), Some("VENDOR" .as_bytes()));
+        assert_eq!(   view.name(), None                      );
+        assert_eq!(view.version(), Some("VERSION".as_bytes()));
+        assert_eq!(view.to_string(), board.to_string());
+        assert_eq!(view.to_board_id().unwrap(), board);
+    }
+
+    #[test]
+    fn detect_into() {
+        let root = crate::fixture::TempDir::new("borrowed-detect-into");
+        root.write("sys/class/dmi/id/board_vendor", "VENDOR\n");
+        root.write("sys/class/dmi/id/board_version", "VERSION\r\n");
+        let mut buf = [0u8; 13];
+        let view = BoardId::detect_into_at(root.path(), &mut buf).unwrap().unwrap();
+        assert_eq!([view.vendor(), view.name(), view.version()], [Some(&b"VENDOR"[..]), None, Some(&b"VERSION"[..])]);
+        let mut short = [0u8; 12];
+        assert_eq!(BoardId::detect_into_at(root.path(), &mut short).unwrap(), Err(BufferTooSmall { needed: 13 }));
+        assert_eq!(BoardId::detect_into_at(root.path(), &mut []).unwrap(), Err(BufferTooSmall { needed: 13 }));
+    }
+
+    #[test]
+    fn stripped_len() {
+        // reads straddling the buffer and the scratch space
+        struct Bytewise<'a>(&'a [u8]);
+        impl Read for Bytewise<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> { self.0.take(1).read(buf).inspect(|&n| self.0 = &self.0[n..]) }
+        }
+        for (input, expected) in [(&b"ABC\r\n"[..], 3), (b"ABC\n", 3), (b"ABC", 3), (b"\n", 0), (b"", 0)] {
+            for len in 0..=input.len() {
+                assert_eq!(read_stripped(input, &mut vec![0; len]).unwrap(), expected);
+                assert_eq!(read_stripped(Bytewise(input), &mut vec![0; len]).unwrap(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn undetected() {
+        let board = BoardId::from_parts(None, None, None).unwrap();
+        let view = BoardIdRef::from(&board);
+        assert_eq!([view.vendor(), view.name(), view.version()], [None; 3]);
+        assert_eq!(view.to_string(), "undetected motherboard");
+    }
+}
@@ -0,0 +1,125 @@
+//! [`BoardIdBuf`], a heap-backed [`BoardId`](crate::BoardId).
+
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+#[cfg(feature = "std")] use std::io::{self, Read};
+
+#[cfg(feature = "std")] use crate::NewlinePolicy;
+use crate::{BoardId, BoardIdRef};
+
+/// A heap-backed motherboard ID, whose parts aren't limited by a fixed buffer.
+#[derive(Debug, Default, Hash, Clone, PartialEq, PartialOrd, Eq, Ord)]
+pub struct BoardIdBuf {
+    /// The parts, back to back.
+    bytes: Vec<u8>,
+    /// The exclusive end for the vendor part.
+    vendor: usize,
+    /// The exclusive end for the name part.
+    name: usize,
+}
+
+impl BoardIdBuf {
+    /// Creates a [`BoardIdBuf`] from its parts.
+    pub fn from_parts(vendor: Option<&[u8]>, name: Option<&[u8]>, version: Option<&[u8]>) -> Self {
+        let mut bytes = Vec::from(vendor.unwrap_or_default());
+        let vendor = bytes.len();
+        bytes.extend_from_slice(name.unwrap_or_default());
+        let name = bytes.len();
+        bytes.extend_from_slice(version.unwrap_or_default());
+        Self { bytes, vendor, name }
+    }
+
+    /// Reads a [`BoardIdBuf`] from streams of its parts, stripping their trailing newline (as per the
+    /// default [`NewlinePolicy`]), allocating at most `max_total` bytes for the parts.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`] as soon as the combined length of the parts
+    /// exceeds `max_total`, having read at most a newline past it.
+    #[cfg(feature = "std")]
+    pub fn from_streams_bounded(max_total: usize, vendor: Option<impl Read>, name: Option<impl Read>, version: Option<impl Read>) -> io::Result<Self> {
+        fn read(bytes: &mut Vec<u8>, stream: Option<impl Read>, max_total: usize) -> io::Result<usize> {
+            let start = bytes.len();
+            if let Some(stream) = stream {
+                // room for a CRLF terminator, which isn't part of the budget
+                stream.take(((max_total - start) as u64).saturating_add(2)).read_to_end(bytes)?;
+                bytes.truncate(start + NewlinePolicy::default().trimmed_len(&bytes[start..]));
+                if bytes.len() > max_total {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, "the motherboard ID exceeds the memory budget"))
+                }
+            }
+            Ok(bytes.len())
+        }
+
+        let mut bytes = Vec::new();
+        let vendor = read(&mut bytes, vendor, max_total)?;
+        let name = read(&mut bytes, name, max_total)?;
+        read(&mut bytes, version, max_total)?;
+        Ok(Self { bytes, vendor, name })
+    }
+
+    /// Gets a borrowed view of the [`BoardIdBuf`].
+    pub fn as_id_ref(&self) -> BoardIdRef<'_> { BoardIdRef::new(&self.bytes, self.vendor, self.name) }
+
+    /// Gets the board's vendor / brand.
+    #[inline]
+    pub fn vendor(&self) -> Option<&[u8]> { self.as_id_ref().vendor() }
+
+    /// Gets the board's name / model.
+    #[inline]
+    pub fn name(&self) -> Option<&[u8]> { self.as_id_ref().name() }
+
+    /// Gets the board's version.
+    #[inline]
+    pub fn version(&self) -> Option<&[u8]> { self.as_id_ref().version() }
+
+    /// Gets the bytes the [`BoardIdBuf`] occupies when persisted minimally: the combined length of
+    /// the parts plus the same fixed overhead as [`BoardId::heap_size`].
+    pub fn heap_size(&self) -> usize { BoardId::ENCODING_OVERHEAD + self.bytes.len() }
+}
+
+/// Same as [`BoardId`](crate::BoardId)'s [`Display`] implementation.
+impl Display for BoardIdBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { self.as_id_ref().fmt(f) }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    const NOENT: Option<&[u8]> = None;
+
+    #[test]
+    fn under_budget() {
+        let board = BoardIdBuf::from_streams_bounded(10, Some("VENDO\n".as_bytes()), Some("NAME\r\n".as_bytes()), Some("V".as_bytes())).unwrap();
+        assert_eq!(board, BoardIdBuf::from_parts(Some(b"VENDO"), Some(b"NAME"), Some(b"V")));
+        let board = BoardIdBuf::from_streams_bounded(9, Some("VENDO\n".as_bytes()), Some("NAME\n".as_bytes()), NOENT).unwrap();
+        assert_eq!(board.to_string(), "VENDO NAME");
+        assert_eq!(board.version(), None);
+    }
+
+    #[test]
+    fn over_budget() {
+        let e = BoardIdBuf::from_streams_bounded(9, Some("VENDO\n".as_bytes()), Some("NAME\n".as_bytes()), Some("V".as_bytes())).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        let e = BoardIdBuf::from_streams_bounded(8, Some("VENDO\n".as_bytes()), Some("NAME\n".as_bytes()), NOENT).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn heap_size() {
+        assert_eq!(BoardIdBuf::from_parts(Some(b"VENDOR"), None, Some(b"VERSION")).heap_size(), 17);
+        assert_eq!(BoardIdBuf::default().heap_size(), 4);
+    }
+
+    #[test]
+    fn unbounded() {
+        let board = BoardIdBuf::from_streams_bounded(usize::MAX, Some("VENDOR\n".as_bytes()), NOENT, Some("V".as_bytes())).unwrap();
+        assert_eq!(board, BoardIdBuf::from_parts(Some(b"VENDOR"), None, Some(b"V")));
+    }
+
+    #[test]
+    fn bounded_read() {
+        // an endless stream only gets read up to the budget
+        let e = BoardIdBuf::from_streams_bounded(100, Some(io::repeat(b'A')), NOENT, NOENT).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+    }
+}
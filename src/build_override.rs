@@ -0,0 +1,47 @@
+//! The build-time identity override of the `build-override` feature.
+
+use std::io;
+
+use crate::BoardId;
+
+/// The override, as `VENDOR|NAME|VERSION`, from the `BOARD_ID_OVERRIDE` environment variable at
+/// compile time.
+const OVERRIDE: Option<&str> = option_env!("BOARD_ID_OVERRIDE");
+
+/// Gets the overriding [`BoardId`], if the override is set.
+///
+/// Fails with [`io::ErrorKind::InvalidData`] if the override is malformed.
+pub(crate) fn board() -> io::Result<Option<BoardId>> { OVERRIDE.map(parse).transpose() }
+
+/// Parses an override `VENDOR|NAME|VERSION`, where empty (or trailing omitted) parts are undetected.
+fn parse(s: &str) -> io::Result<BoardId> {
+    let mut parts = s.split('|').map(|part| Some(part.as_bytes()).filter(|part| !part.is_empty()));
+    let [vendor, name, version] = [(); 3].map(|()| parts.next().flatten());
+    if parts.next().is_some() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "BOARD_ID_OVERRIDE has more than 3 parts"))
+    }
+    Ok(BoardId::from_parts(vendor, name, version)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Source;
+
+    #[test]
+    fn parse() {
+        assert_eq!(super::parse("VENDOR|NAME|VERSION").unwrap(), BoardId::from_parts(Some(b"VENDOR"), Some(b"NAME"), Some(b"VERSION")).unwrap());
+        assert_eq!(super::parse("VENDOR||VERSION").unwrap(), BoardId::from_parts(Some(b"VENDOR"), None, Some(b"VERSION")).unwrap());
+        assert_eq!(super::parse("VENDOR").unwrap(), BoardId::from_parts(Some(b"VENDOR"), None, None).unwrap());
+        assert_eq!(super::parse("A|B|C|D").unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    /// Only meaningful when built with `BOARD_ID_OVERRIDE` set.
+    #[test]
+    fn override_wins() {
+        let Some(expected) = OVERRIDE else { return };
+        let expected = super::parse(expected).unwrap();
+        assert_eq!(BoardId::detect().unwrap(), expected);
+        assert_eq!(BoardId::detect_with_provenance().unwrap(), (expected, Some(Source::BuildOverride)));
+    }
+}
@@ -0,0 +1,166 @@
+//! Caching the [`BoardId`] on disk, for when live detection isn't available (e.g. early boot,
+//! before sysfs is mounted).
+
+use std::{fs::{self, OpenOptions}, io::{self, Write}, path::Path, sync::atomic::{AtomicU64, Ordering}};
+#[cfg(unix)] use std::os::unix::fs::OpenOptionsExt;
+
+use crate::BoardId;
+
+/// Where a [`BoardId`] came from.
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub enum Origin {
+    /// Detected from the live system.
+    Live,
+    /// Loaded from the cache file.
+    Cache,
+}
+
+/// Writes the bytes to the file at the given path atomically: they're written to a temporary file
+/// next to the target which is then renamed over it. The file is only readable and writable by its
+/// owner (mode 0600, on Unix).
+///
+/// The temporary file is unique to the call, so concurrent writes (even from one process) don't
+/// share it. On Unix, the directory is synced after the rename so that the rename is durable.
+pub(crate) fn write_atomic(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    static CALLS: AtomicU64 = AtomicU64::new(0);
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(".{}.{}.tmp", std::process::id(), CALLS.fetch_add(1, Ordering::Relaxed)));
+    let result = (|| {
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)] options.mode(0o600);
+        let mut file = options.open(&tmp)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+        fs::rename(&tmp, path)
+    })();
+    if result.is_err() { let _ = fs::remove_file(&tmp); }
+    result?;
+    #[cfg(unix)] {
+        let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+        fs::File::open(dir)?.sync_all()?;
+    }
+    Ok(())
+}
+
+impl BoardId {
+    /// Loads a [`BoardId`] previously [stored](Self::store_cached) at the given path.
+    ///
+    /// Returns `Ok(None)` if the file doesn't exist or is corrupt.
+    pub fn load_cached(path: impl AsRef<Path>) -> io::Result<Option<Self>> {
+        match fs::read(path) {
+            Ok(bytes) => Ok(Self::from_bytes(&bytes).ok()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Stores the [`BoardId`] at the given path in its [stable binary encoding](Self::to_bytes).
+    ///
+    /// The write is atomic: the encoding is written to a temporary file next to the target which is
+    /// then renamed over it. The file is only readable and writable by its owner (mode 0600, on Unix).
+    pub fn store_cached(&self, path: impl AsRef<Path>) -> io::Result<()> { write_atomic(path.as_ref(), &self.to_bytes()) }
+
+    /// Attempts to detect the [`BoardId`], falling back to the cache at the given path.
+    ///
+    /// Live detection is preferred, and when it detects anything the cache is refreshed with its
+    /// result (failing to refresh the cache is ignored). When live detection fails or detects
+    /// nothing, the cached [`BoardId`] is used if there's a valid one, otherwise the live result is
+    /// returned as-is.
+    pub fn detect_with_cache(path: impl AsRef<Path>) -> io::Result<(Self, Origin)> {
+        Self::detect_or_cached(Self::detect(), path.as_ref())
+    }
+
+    /// The logic of [`Self::detect_with_cache`], given the live detection result.
+    fn detect_or_cached(live: io::Result<Self>, path: &Path) -> io::Result<(Self, Origin)> {
+        if let Ok(board) = &live {
+            if !board.is_undetected() {
+                let _ = board.store_cached(path);
+                return Ok((*board, Origin::Live));
+            }
+        }
+        match Self::load_cached(path) {
+            Ok(Some(cached)) => Ok((cached, Origin::Cache)),
+            _ => live.map(|board| (board, Origin::Live)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(unix)] use std::os::unix::fs::PermissionsExt;
+
+    use super::*;
+    use crate::fixture::TempDir;
+
+    fn board() -> BoardId {
+        BoardId::from_streams(Some("VENDOR\n".as_bytes()), Some("NAME\n".as_bytes()), Some("VERSION\n".as_bytes())).unwrap()
+    }
+
+    fn undetected() -> BoardId {
+        BoardId::from_streams(None::<&[u8]>, None::<&[u8]>, None::<&[u8]>).unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let dir = TempDir::new("cache-round-trip");
+        let path = dir.path().join("board");
+        board().store_cached(&path).unwrap();
+        assert_eq!(BoardId::load_cached(&path).unwrap(), Some(board()));
+        #[cfg(unix)] assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1, "temporary file left behind");
+    }
+
+    #[test]
+    fn concurrent() {
+        let dir = TempDir::new("cache-concurrent");
+        let path = dir.path().join("board");
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| for _ in 0..20 { board().store_cached(&path).unwrap() });
+            }
+        });
+        assert_eq!(BoardId::load_cached(&path).unwrap(), Some(board()));
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1, "temporary file left behind");
+    }
+
+    #[test]
+    fn missing() {
+        let dir = TempDir::new("cache-missing");
+        assert_eq!(BoardId::load_cached(dir.path().join("board")).unwrap(), None);
+    }
+
+    #[test]
+    fn corrupt() {
+        let dir = TempDir::new("cache-corrupt");
+        let path = dir.write("board", "garbage");
+        assert_eq!(BoardId::load_cached(&path).unwrap(), None);
+        assert_eq!(BoardId::detect_or_cached(Ok(undetected()), &path).unwrap(), (undetected(), Origin::Live));
+    }
+
+    #[test]
+    fn prefers_live() {
+        let dir = TempDir::new("cache-prefers-live");
+        let path = dir.path().join("board");
+        undetected().store_cached(&path).unwrap();
+        assert_eq!(BoardId::detect_or_cached(Ok(board()), &path).unwrap(), (board(), Origin::Live));
+        assert_eq!(BoardId::load_cached(&path).unwrap(), Some(board()), "cache wasn't refreshed");
+    }
+
+    #[test]
+    fn falls_back() {
+        let dir = TempDir::new("cache-falls-back");
+        let path = dir.path().join("board");
+        board().store_cached(&path).unwrap();
+        assert_eq!(BoardId::detect_or_cached(Ok(undetected()), &path).unwrap(), (board(), Origin::Cache));
+        let failed = Err(io::Error::new(io::ErrorKind::PermissionDenied, "denied"));
+        assert_eq!(BoardId::detect_or_cached(failed, &path).unwrap(), (board(), Origin::Cache));
+    }
+
+    #[test]
+    fn no_fallback() {
+        let dir = TempDir::new("cache-no-fallback");
+        let failed = Err(io::Error::new(io::ErrorKind::PermissionDenied, "denied"));
+        assert_eq!(BoardId::detect_or_cached(failed, &dir.path().join("board")).unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+    }
+}
@@ -0,0 +1,84 @@
+//! Extracting the chipset from [`BoardId`] names.
+
+use crate::BoardId;
+
+/// The chipset tokens [`BoardId::chipset`] recognizes.
+///
+/// Suffixed variants come before their base (e.g. "X670E" before "X670") so the most specific one
+/// matches. To recognize other tokens, use [`BoardId::chipset_in`] with an extended list.
+pub const CHIPSETS: &[&str] = &[
+    // Intel desktop
+    "Z890", "B860", "H810",
+    "Z790", "B760", "H770",
+    "Z690", "B660", "H670", "H610",
+    "Z590", "B560", "H570", "H510",
+    "Z490", "B460", "H470", "H410",
+    "Z390", "B365", "Z370", "B360", "H370", "H310",
+    "Z270", "B250", "H270", "Z170", "B150", "H170", "H110",
+    // Intel workstation / HEDT
+    "W790", "W680", "X299", "X99", "C621",
+    // AMD desktop
+    "X870E", "X870", "B850", "B840",
+    "X670E", "X670", "B650E", "B650", "A620",
+    "X570", "B550", "A520",
+    "X470", "B450", "X370", "B350", "A320",
+    // AMD workstation / HEDT
+    "TRX50", "TRX40", "WRX90", "WRX80", "X399",
+];
+
+impl BoardId {
+    /// Gets the first (leftmost) [known chipset](CHIPSETS) token in the board's name, e.g. "B550"
+    /// for "PRIME B550M-A".
+    ///
+    /// See [`Self::chipset_in`].
+    pub fn chipset(&self) -> Option<&'static str> { self.chipset_in(CHIPSETS) }
+
+    /// Gets the first (leftmost) of the given chipset tokens in the board's name.
+    ///
+    /// Tokens match case-insensitively, where they aren't preceded by a letter or digit nor followed
+    /// by a digit (so form factor suffixes are allowed, e.g. "B550" in "B550M"). When several tokens
+    /// match at the same position, the first one in the list wins.
+    pub fn chipset_in<'a>(&self, tokens: &[&'a str]) -> Option<&'a str> {
+        let name = self.name()?;
+        (0..name.len())
+            .filter(|&i| i == 0 || !name[i - 1].is_ascii_alphanumeric())
+            .find_map(|i| tokens.iter().copied().find(|token| {
+                let rest = &name[i..];
+                rest.len() >= token.len()
+                    && rest[..token.len()].eq_ignore_ascii_case(token.as_bytes())
+                    && !rest.get(token.len()).is_some_and(u8::is_ascii_digit)
+            }))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    fn chipset(name: &str) -> Option<&'static str> {
+        BoardId::from_parts(None, Some(name.as_bytes()), None).unwrap().chipset()
+    }
+
+    #[test]
+    fn known() {
+        assert_eq!(chipset("ROG STRIX Z790-E GAMING WIFI"), Some("Z790"));
+        assert_eq!(chipset("PRIME B550M-A"), Some("B550"));
+        assert_eq!(chipset("X670E AORUS MASTER"), Some("X670E"));
+        assert_eq!(chipset("mag b650 tomahawk"), Some("B650"));
+    }
+
+    #[test]
+    fn none() {
+        assert_eq!(chipset("0UW306"), None);
+        assert_eq!(chipset("AB3500"), None);
+        assert_eq!(chipset("Z7900"), None);
+        assert_eq!(BoardId::from_parts(Some(b"Z790"), None, None).unwrap().chipset(), None);
+    }
+
+    #[test]
+    fn extended() {
+        let board = BoardId::from_parts(None, Some(b"Sabertooth 990FX"), None).unwrap();
+        assert_eq!(board.chipset(), None);
+        assert_eq!(board.chipset_in(&[CHIPSETS, &["990FX"]].concat()), Some("990FX"));
+    }
+}
@@ -0,0 +1,151 @@
+//! [`BoardId::known_chipset`], the [`Chipset`] of a board from a compiled-in table and its name.
+
+use crate::{glob_match, BoardId};
+
+/// The vendor of a [`Chipset`].
+#[derive(Debug, Hash, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
+pub enum ChipsetVendor {
+    /// Intel.
+    Intel,
+    /// AMD.
+    Amd,
+}
+
+/// Defines [`Chipset`] from its vendors, variants and tokens.
+macro_rules! chipsets {
+    ($($vendor:ident $variant:ident $token:literal,)*) => {
+        /// A motherboard chipset.
+        #[derive(Debug, Hash, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
+        pub enum Chipset {
+            $(#[doc = concat!("The ", stringify!($vendor), " ", $token, ".")] $variant,)*
+        }
+
+        impl Chipset {
+            /// Every chipset.
+            pub const ALL: &'static [Self] = &[$(Self::$variant,)*];
+
+            /// Gets the chipset's token, as it appears in board names, e.g. "B550".
+            pub const fn token(self) -> &'static str { match self { $(Self::$variant => $token,)* } }
+
+            /// Gets the chipset's vendor.
+            pub const fn vendor(self) -> ChipsetVendor { match self { $(Self::$variant => ChipsetVendor::$vendor,)* } }
+        }
+    };
+}
+
+chipsets! {
+    Intel IntelZ890  "Z890" , Intel IntelB860  "B860" , Intel IntelH810  "H810" ,
+    Intel IntelZ790  "Z790" , Intel IntelB760  "B760" , Intel IntelH770  "H770" ,
+    Intel IntelZ690  "Z690" , Intel IntelB660  "B660" , Intel IntelH670  "H670" , Intel IntelH610 "H610",
+    Intel IntelZ590  "Z590" , Intel IntelB560  "B560" , Intel IntelH570  "H570" , Intel IntelH510 "H510",
+    Intel IntelZ490  "Z490" , Intel IntelB460  "B460" , Intel IntelH470  "H470" , Intel IntelH410 "H410",
+    Intel IntelZ390  "Z390" , Intel IntelB365  "B365" , Intel IntelZ370  "Z370" , Intel IntelB360 "B360",
+    Intel IntelH370  "H370" , Intel IntelH310  "H310" ,
+    Intel IntelZ270  "Z270" , Intel IntelB250  "B250" , Intel IntelH270  "H270" , Intel IntelZ170 "Z170",
+    Intel IntelB150  "B150" , Intel IntelH170  "H170" , Intel IntelH110  "H110" ,
+    Intel IntelW790  "W790" , Intel IntelW680  "W680" , Intel IntelX299  "X299" , Intel IntelX99  "X99" ,
+    Intel IntelC621  "C621" ,
+    Amd   AmdX870E   "X870E", Amd   AmdX870    "X870" , Amd   AmdB850    "B850" , Amd   AmdB840   "B840",
+    Amd   AmdX670E   "X670E", Amd   AmdX670    "X670" , Amd   AmdB650E   "B650E", Amd   AmdB650   "B650",
+    Amd   AmdA620    "A620" ,
+    Amd   AmdX570    "X570" , Amd   AmdB550    "B550" , Amd   AmdA520    "A520" ,
+    Amd   AmdX470    "X470" , Amd   AmdB450    "B450" , Amd   AmdX370    "X370" , Amd   AmdB350   "B350",
+    Amd   AmdA320    "A320" ,
+    Amd   AmdTrx50   "TRX50", Amd   AmdTrx40   "TRX40", Amd   AmdWrx90   "WRX90", Amd   AmdWrx80  "WRX80",
+    Amd   AmdX399    "X399" ,
+}
+
+/// Board name globs (matched case-insensitively) and their chipsets, for names that don't embed
+/// the chipset token. The first matching glob wins, so specific globs come before general ones.
+const NAME_PATTERNS: &[(&str, Chipset)] = &[
+    // ASUS ROG names its flagship lines by generation
+    ("*CROSSHAIR X670E*"   , Chipset::AmdX670E),
+    ("*CROSSHAIR VIII*"    , Chipset::AmdX570),
+    ("*CROSSHAIR VII HERO*", Chipset::AmdX470),
+    ("*CROSSHAIR VI HERO*" , Chipset::AmdX370),
+    ("*MAXIMUS XIII*"      , Chipset::IntelZ590),
+    ("*MAXIMUS XII*"       , Chipset::IntelZ490),
+    ("*MAXIMUS XI *"       , Chipset::IntelZ390),
+    ("*ZENITH II*"         , Chipset::AmdTrx40),
+    ("*ZENITH EXTREME*"    , Chipset::AmdX399),
+    ("*RAMPAGE VI*"        , Chipset::IntelX299),
+    // MSI boards that only report their MS-XXXX code
+    ("MS-7C91*"            , Chipset::AmdB550),
+    ("MS-7C56*"            , Chipset::AmdB550),
+    ("MS-7C37*"            , Chipset::AmdX570),
+    ("MS-7C02*"            , Chipset::AmdB450),
+    ("MS-7D25*"            , Chipset::IntelZ690),
+    ("MS-7E12*"            , Chipset::IntelZ790),
+];
+
+impl Chipset {
+    /// Gets the chipset whose [token](Self::token) is the given one, case-insensitively.
+    pub fn from_token(token: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|chipset| chipset.token().eq_ignore_ascii_case(token))
+    }
+}
+
+impl BoardId {
+    /// Gets the board's [`Chipset`].
+    ///
+    /// The board's name is first looked up in a compiled-in table of names that don't embed their
+    /// chipset (e.g. "ROG CROSSHAIR VIII HERO" is an X570), and when it's not there, the chipset
+    /// token embedded in the name is extracted as per [`Self::chipset`].
+    pub fn known_chipset(&self) -> Option<Chipset> {
+        let name = self.name()?.to_ascii_lowercase();
+        let hit = NAME_PATTERNS.iter().find(|(glob, _)| glob_match(glob.to_ascii_lowercase().as_bytes(), &name));
+        match hit {
+            Some(&(_, chipset)) => Some(chipset),
+            None => Chipset::from_token(self.chipset()?),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+    use crate::CHIPSETS;
+
+    fn chipset(name: &str) -> Option<Chipset> {
+        BoardId::from_parts(None, Some(name.as_bytes()), None).unwrap().known_chipset()
+    }
+
+    #[test]
+    fn tokens() {
+        // every token Self::chipset extracts has a chipset
+        assert_eq!(Chipset::ALL.iter().map(|chipset| chipset.token()).collect::<Vec<_>>(), CHIPSETS);
+        assert_eq!(Chipset::from_token("b550"), Some(Chipset::AmdB550));
+        assert_eq!(Chipset::IntelZ790.vendor(), ChipsetVendor::Intel);
+        assert_eq!(Chipset::AmdTrx40.vendor(), ChipsetVendor::Amd);
+    }
+
+    #[test]
+    fn corpus() {
+        for (name, expected) in [
+            ("PRIME B550-PLUS"             , Some(Chipset::AmdB550)),
+            ("ROG STRIX Z790-E GAMING WIFI", Some(Chipset::IntelZ790)),
+            ("X570 AORUS ELITE"            , Some(Chipset::AmdX570)),
+            ("X670E AORUS MASTER"          , Some(Chipset::AmdX670E)),
+            ("TRX40 AORUS XTREME"          , Some(Chipset::AmdTrx40)),
+            ("MAG B650 TOMAHAWK WIFI (MS-7D75)", Some(Chipset::AmdB650)),
+            ("ROG CROSSHAIR VIII HERO (WI-FI)" , Some(Chipset::AmdX570)),
+            ("ROG CROSSHAIR VII HERO"      , Some(Chipset::AmdX470)),
+            ("ROG MAXIMUS XIII HERO"       , Some(Chipset::IntelZ590)),
+            ("ROG MAXIMUS XII FORMULA"     , Some(Chipset::IntelZ490)),
+            ("ROG ZENITH II EXTREME ALPHA" , Some(Chipset::AmdTrx40)),
+            ("MS-7C91"                     , Some(Chipset::AmdB550)),
+            ("ms-7c37"                     , Some(Chipset::AmdX570)),
+            ("0XR032"                      , None),
+            ("Raspberry Pi 4 Model B Rev 1.4", None),
+        ] {
+            assert_eq!(chipset(name), expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn table_first() {
+        // the table hit wins over the embedded token
+        assert_eq!(chipset("ROG CROSSHAIR X670E HERO"), Some(Chipset::AmdX670E));
+        assert_eq!(BoardId::from_parts(Some(b"X570"), None, None).unwrap().known_chipset(), None);
+    }
+}
@@ -0,0 +1,62 @@
+//! [`DetectContext`], a detection bundled with the environment info bug triage asks for.
+
+use std::{fs, io, path::Path};
+
+use crate::BoardId;
+
+/// The kernel release file, relative to the filesystem root.
+const OSRELEASE: &str = "proc/sys/kernel/osrelease";
+
+/// A [`BoardId`] detection along with its environment.
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub struct DetectContext {
+    /// The detected board.
+    pub board: BoardId,
+    /// The kernel release (as per `uname -r`), or `None` if it couldn't be read.
+    pub kernel_release: Option<String>,
+}
+
+impl BoardId {
+    /// Attempts to detect the [`BoardId`], along with the kernel release.
+    ///
+    /// See [`Self::detect_with_context_at`].
+    pub fn detect_with_context() -> io::Result<DetectContext> { Self::detect_with_context_at("/") }
+
+    /// Attempts to detect the [`BoardId`] of the system whose filesystem is rooted at the given
+    /// path, along with its kernel release (from `proc/sys/kernel/osrelease` under the root).
+    ///
+    /// Only detecting the board can fail: the kernel release is best-effort, so when it's missing
+    /// or unreadable it's `None`.
+    pub fn detect_with_context_at(root: impl AsRef<Path>) -> io::Result<DetectContext> {
+        let root = root.as_ref();
+        let kernel_release = fs::read(root.join(OSRELEASE)).ok()
+            .map(|release| String::from_utf8_lossy(release.trim_ascii()).into_owned())
+            .filter(|release| !release.is_empty());
+        Ok(DetectContext { board: Self::detect_at(root)?, kernel_release })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    #[test]
+    fn kernel_release() {
+        let root = TempDir::new("context");
+        root.write("sys/class/dmi/id/board_name", "NAME\n");
+        root.write("proc/sys/kernel/osrelease", "6.8.0-45-generic\n");
+        let context = BoardId::detect_with_context_at(root.path()).unwrap();
+        assert_eq!(context.board.name(), Some(&b"NAME"[..]));
+        assert_eq!(context.kernel_release.as_deref(), Some("6.8.0-45-generic"));
+    }
+
+    #[test]
+    fn missing_kernel_release() {
+        let root = TempDir::new("context-missing");
+        root.write("sys/class/dmi/id/board_name", "NAME\n");
+        let context = BoardId::detect_with_context_at(root.path()).unwrap();
+        assert_eq!(context.kernel_release, None);
+        assert_eq!(context.board.name(), Some(&b"NAME"[..]));
+    }
+}
@@ -0,0 +1,136 @@
+//! [`BoardId::cross_check`], checking the sysfs DMI values against `dmidecode`'s.
+
+use std::{fmt::{self, Display}, io, path::Path, process::Command};
+
+use crate::{pin::PartValue, BoardId, Part};
+
+/// Runs the external commands [`BoardId::cross_check_with`] needs, so they can be mocked.
+pub trait CommandRunner {
+    /// Runs the program with the arguments, and gets its standard output.
+    ///
+    /// Fails if the program couldn't run or exited unsuccessfully.
+    fn run(&mut self, program: &str, args: &[&str]) -> io::Result<Vec<u8>>;
+}
+
+/// The [`CommandRunner`] that runs commands on the system.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn run(&mut self, program: &str, args: &[&str]) -> io::Result<Vec<u8>> {
+        let output = Command::new(program).args(args).output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(io::Error::other(format!("{program} failed ({}): {}", output.status, stderr.trim())))
+        }
+        Ok(output.stdout)
+    }
+}
+
+/// The result of [`BoardId::cross_check`]: the [`BoardId`] as read from sysfs and as reported by
+/// `dmidecode`.
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub struct CrossCheck {
+    /// The board as read from the sysfs DMI attributes.
+    pub sysfs: BoardId,
+    /// The board as reported by `dmidecode` (undetected if it reported no baseboard).
+    pub dmidecode: BoardId,
+}
+
+impl CrossCheck {
+    /// Checks whether the sources agree on the part, ignoring surrounding whitespace (which
+    /// `dmidecode` trims).
+    pub fn agrees(&self, part: Part) -> bool {
+        fn trimmed(board: &BoardId, part: Part) -> Option<&[u8]> { board.part(part).map(<[u8]>::trim_ascii).filter(|value| !value.is_empty()) }
+        trimmed(&self.sysfs, part) == trimmed(&self.dmidecode, part)
+    }
+
+    /// Gets the parts the sources disagree on, in vendor, name, version order.
+    pub fn mismatches(&self) -> Vec<Part> { Part::ALL.into_iter().filter(|&part| !self.agrees(part)).collect() }
+}
+
+/// Lists each part's values and whether they agree, one part per line.
+impl Display for CrossCheck {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for part in Part::ALL {
+            writeln!(f, "{part}: {} sysfs {}, dmidecode {}", if self.agrees(part) { "agree" } else { "DISAGREE" }, PartValue(self.sysfs.part(part)), PartValue(self.dmidecode.part(part)))?;
+        }
+        Ok(())
+    }
+}
+
+impl BoardId {
+    /// Reads the [`BoardId`] from the sysfs DMI attributes and from `dmidecode -t baseboard`, to
+    /// check them against each other, e.g. to catch kernels exposing stale DMI data.
+    ///
+    /// `dmidecode` usually needs root. See [`Self::cross_check_with`].
+    pub fn cross_check() -> io::Result<CrossCheck> { Self::cross_check_with("/", &mut SystemRunner) }
+
+    /// Reads the [`BoardId`] from the sysfs DMI attributes of the system whose filesystem is rooted
+    /// at the given path, and from `dmidecode -t baseboard` as run by the given runner.
+    ///
+    /// Only DMI is read from sysfs, with no device tree fallback. Fails if either read does, or if
+    /// `dmidecode`'s output doesn't fit in a [`BoardId`].
+    pub fn cross_check_with(root: impl AsRef<Path>, runner: &mut impl CommandRunner) -> io::Result<CrossCheck> {
+        let (sysfs, _) = Self::detect_with_lengths_at(root)?;
+        let output = runner.run("dmidecode", &["-t", "baseboard"])?;
+        let dmidecode = Self::from_dmidecode_text(&String::from_utf8_lossy(&output))?;
+        Ok(CrossCheck { sysfs, dmidecode: dmidecode.map_or_else(|| Self::from_parts(None, None, None), Ok)? })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    /// A runner that outputs the given text for `dmidecode -t baseboard`.
+    struct Mock(&'static str);
+
+    impl CommandRunner for Mock {
+        fn run(&mut self, program: &str, args: &[&str]) -> io::Result<Vec<u8>> {
+            assert_eq!((program, args), ("dmidecode", &["-t", "baseboard"][..]));
+            Ok(self.0.as_bytes().to_vec())
+        }
+    }
+
+    const DMIDECODE: &str = "\
+# dmidecode 3.3
+Handle 0x0002, DMI type 2, 15 bytes
+Base Board Information
+\tManufacturer: ASUSTeK COMPUTER INC.
+\tProduct Name: PRIME B550-PLUS
+\tVersion: Rev X.0x
+";
+
+    fn root(name: &str, version: &str) -> TempDir {
+        let root = TempDir::new(name);
+        root.write("sys/class/dmi/id/board_vendor", "ASUSTeK COMPUTER INC.\n");
+        root.write("sys/class/dmi/id/board_name", "PRIME B550-PLUS   \n");
+        root.write("sys/class/dmi/id/board_version", version);
+        root
+    }
+
+    #[test]
+    fn agree() {
+        let root = root("cross-check-agree", "Rev X.0x\n");
+        let check = BoardId::cross_check_with(root.path(), &mut Mock(DMIDECODE)).unwrap();
+        assert_eq!(check.mismatches(), []);
+    }
+
+    #[test]
+    fn version_disagrees() {
+        let root = root("cross-check-disagree", "Rev 1.xx\n");
+        let check = BoardId::cross_check_with(root.path(), &mut Mock(DMIDECODE)).unwrap();
+        assert_eq!(check.mismatches(), [Part::Version]);
+        assert!(check.agrees(Part::Vendor) && check.agrees(Part::Name));
+        assert!(check.to_string().contains("version: DISAGREE sysfs \"Rev 1.xx\", dmidecode \"Rev X.0x\""));
+    }
+
+    #[test]
+    fn no_baseboard() {
+        let root = root("cross-check-no-baseboard", "Rev X.0x\n");
+        let check = BoardId::cross_check_with(root.path(), &mut Mock("# dmidecode 3.3\n")).unwrap();
+        assert_eq!(check.mismatches(), Part::ALL);
+    }
+}
@@ -0,0 +1,51 @@
+//! NUL-terminated copies of the [`BoardId`] parts, for C consumers.
+
+use std::ffi::{CString, NulError};
+
+use crate::BoardId;
+
+/// The parts of a [`BoardId`] as NUL-terminated strings.
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub struct BoardIdC {
+    /// The board's vendor / brand.
+    pub vendor: Option<CString>,
+    /// The board's name / model.
+    pub name: Option<CString>,
+    /// The board's version.
+    pub version: Option<CString>,
+}
+
+impl BoardId {
+    /// Copies the parts into NUL-terminated strings.
+    ///
+    /// Fails if a part contains a NUL byte.
+    pub fn to_c(&self) -> Result<BoardIdC, NulError> {
+        let c = |part: Option<&[u8]>| part.map(CString::new).transpose();
+        Ok(BoardIdC {
+             vendor: c(self.vendor ())?,
+               name: c(self.name   ())?,
+            version: c(self.version())?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parts() {
+        let board = BoardId::from_parts(Some(b"VENDOR"), None, Some(b"VERSION")).unwrap();
+        let c = board.to_c().unwrap();
+        assert_eq!(c .vendor.as_deref().map(|s| s.to_bytes()), board.vendor ());
+        assert_eq!(c   .name.as_deref().map(|s| s.to_bytes()), board.name   ());
+        assert_eq!(c.version.as_deref().map(|s| s.to_bytes()), board.version());
+        assert_eq!(c.vendor.unwrap().as_bytes_with_nul(), b"VENDOR\0");
+    }
+
+    #[test]
+    fn interior_nul() {
+        let board = BoardId::from_parts(None, Some(b"NA\0ME"), None).unwrap();
+        assert_eq!(board.to_c().unwrap_err().nul_position(), 2);
+    }
+}
@@ -0,0 +1,87 @@
+//! [`BoardId`] detection from the device tree, for boards without DMI (e.g. ARM SBCs).
+
+use std::io::{self, Read};
+
+use crate::{BoardId, CapacityError};
+
+/// The device tree directories, relative to the filesystem root, in the order they're tried.
+pub(crate) const DIRS: [&str; 2] = ["proc/device-tree", "sys/firmware/devicetree/base"];
+
+/// Reads a device tree property into the buffer, without the trailing NUL, limited to what can fit
+/// in a [`BoardId`].
+pub(crate) fn read_property(stream: Option<impl Read>, buf: &mut Vec<u8>) -> io::Result<()> {
+    buf.clear();
+    if let Some(stream) = stream { stream.take(BoardId::BUFSZ as u64 + 1).read_to_end(buf)?; }
+    let len = buf.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+    buf.truncate(len);
+    Ok(())
+}
+
+impl BoardId {
+    /// Attempts to make a [`BoardId`] from the device tree `model` and `compatible` properties
+    /// (without their trailing NUL).
+    ///
+    /// The model is the board's name. The compatible property is a NUL-separated list of
+    /// `manufacturer,model` strings, most specific first; the manufacturer of the first one is
+    /// the board's vendor.
+    pub(crate) fn from_device_tree(model: &[u8], compatible: &[u8]) -> Result<Self, CapacityError> {
+        let first = compatible.split(|&b| b == 0).next().unwrap_or_default();
+        let vendor = first.iter().position(|&b| b == b',').map(|i| &first[..i]);
+        Self::from_parts(vendor, Some(model), None)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    /// The `model` of a Raspberry Pi 4.
+    const MODEL: &[u8] = b"Raspberry Pi 4 Model B Rev 1.4\0";
+    /// The `compatible` of a Raspberry Pi 4.
+    const COMPATIBLE: &[u8] = b"raspberrypi,4-model-b\0brcm,bcm2711\0";
+
+    fn from_device_tree(model: Option<&[u8]>, compatible: Option<&[u8]>) -> io::Result<BoardId> {
+        let (mut model_buf, mut compatible_buf) = (Vec::new(), Vec::new());
+        read_property(model, &mut model_buf)?;
+        read_property(compatible, &mut compatible_buf)?;
+        Ok(BoardId::from_device_tree(&model_buf, &compatible_buf)?)
+    }
+
+    #[test]
+    fn model_and_compatible() {
+        let board = from_device_tree(Some(MODEL), Some(COMPATIBLE)).unwrap();
+        assert_eq!( board.vendor(), Some("raspberrypi".as_bytes()));
+        assert_eq!(   board.name(), Some("Raspberry Pi 4 Model B Rev 1.4".as_bytes()));
+        assert_eq!(board.version(), None);
+    }
+
+    #[test]
+    fn only_model() {
+        let board = from_device_tree(Some(MODEL), None).unwrap();
+        assert_eq!(board.vendor(), None);
+        assert_eq!(  board.name(), Some("Raspberry Pi 4 Model B Rev 1.4".as_bytes()));
+    }
+
+    #[test]
+    fn unparseable_compatible() {
+        let board = from_device_tree(Some(MODEL), Some(b"generic\0")).unwrap();
+        assert_eq!(board.vendor(), None);
+    }
+
+    #[test]
+    fn fallback() {
+        let root = TempDir::new("devicetree-fallback");
+        root.write("sys/firmware/devicetree/base/model", MODEL);
+        root.write("sys/firmware/devicetree/base/compatible", COMPATIBLE);
+        assert_eq!(BoardId::detect_at(root.path()).unwrap().to_string(), "raspberrypi Raspberry Pi 4 Model B Rev 1.4");
+    }
+
+    #[test]
+    fn dmi_preferred() {
+        let root = TempDir::new("devicetree-dmi-preferred");
+        root.write("proc/device-tree/model", MODEL);
+        root.write("sys/class/dmi/id/board_name", "NAME\n");
+        assert_eq!(BoardId::detect_at(root.path()).unwrap().name(), Some("NAME".as_bytes()));
+    }
+}
@@ -0,0 +1,254 @@
+//! [`DiagnosticReport`], a dry run of detection for bug reports.
+
+use std::{
+    fmt::{self, Display, Write},
+    fs,
+    hash::Hasher,
+    io,
+    path::{Path, PathBuf},
+};
+#[cfg(unix)] use std::os::unix::fs::PermissionsExt;
+
+use crate::{dmi, fingerprint::Fnv1a, json::push_string, scan::{SourceKind, SOURCES}, BoardId, DmiField, NewlinePolicy};
+
+/// A file's contents, as shown in a [`DiagnosticReport`].
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub enum DiagnosticValue {
+    /// The contents, after trimming.
+    Plain(Vec<u8>),
+    /// The contents of a sensitive file (e.g. a serial), reduced to their length and hash.
+    Redacted {
+        /// The length of the contents, after trimming.
+        len: usize,
+        /// The 64-bit FNV-1a hash of the contents, after trimming, which is stable across releases
+        /// and platforms.
+        hash: u64,
+    },
+}
+
+/// What happened with a file detection tried, in a [`DiagnosticReport`].
+#[derive(Debug)]
+pub struct FileDiagnostic {
+    /// The path of the file.
+    pub path: PathBuf,
+    /// Whether it exists.
+    pub exists: bool,
+    /// Its permission bits, if its metadata was read (on Unix).
+    pub mode: Option<u32>,
+    /// Its size as reported by its metadata (which is the page size for sysfs attributes).
+    pub size: Option<u64>,
+    /// The number of bytes read.
+    pub read: usize,
+    /// The number of trailing bytes trimmed (e.g. newline, NUL).
+    pub trimmed: usize,
+    /// The contents, if read.
+    pub value: Option<DiagnosticValue>,
+    /// The error encountered, if any.
+    pub error: Option<io::Error>,
+}
+
+/// A dry run of detection, recording for every file of every source what was found.
+///
+/// It's meant to be pasted into bug reports, through its [`Display`] implementation. Values of
+/// sensitive files (see [`DmiField::is_root_only`]) are only shown as a length and a hash.
+///
+/// Besides the files detection tries, it records the board serial, which detection doesn't read,
+/// to tell whether root-only attributes are readable.
+#[derive(Debug)]
+pub struct DiagnosticReport {
+    /// The files, in the order detection tries them, followed by the board serial.
+    pub files: Vec<FileDiagnostic>,
+    /// The result of the detection.
+    pub board: io::Result<BoardId>,
+}
+
+/// Diagnoses detection of the system. See [`DiagnosticReport`].
+pub fn diagnose() -> DiagnosticReport { diagnose_at("/") }
+
+/// Diagnoses detection of the system whose filesystem is rooted at the given path. See
+/// [`DiagnosticReport`].
+pub fn diagnose_at(root: impl AsRef<Path>) -> DiagnosticReport {
+    let root = root.as_ref();
+    let files = SOURCES.iter()
+        .flat_map(|source| source.files.iter().map(move |&file| (source.kind, file, root.join(source.dir).join(file))))
+        .chain([(SourceKind::Dmi, DmiField::BoardSerial.attribute(), root.join(dmi::DIR).join(DmiField::BoardSerial.attribute()))])
+        .map(|(kind, file, path)| diagnose_file(kind, file, path))
+        .collect();
+    DiagnosticReport { files, board: BoardId::detect_at(root) }
+}
+
+/// Diagnoses a file of a source of the given kind.
+fn diagnose_file(kind: SourceKind, file: &str, path: PathBuf) -> FileDiagnostic {
+    let mut diagnostic = FileDiagnostic { path, exists: false, mode: None, size: None, read: 0, trimmed: 0, value: None, error: None };
+    match fs::metadata(&diagnostic.path) {
+        Ok(metadata) => {
+            diagnostic.exists = true;
+            #[cfg(unix)] { diagnostic.mode = Some(metadata.permissions().mode() & 0o7777) }
+            diagnostic.size = Some(metadata.len());
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return diagnostic,
+        Err(e) => {
+            diagnostic.exists = true;
+            diagnostic.error  = Some(e);
+            return diagnostic;
+        }
+    }
+    let mut bytes = match fs::read(&diagnostic.path) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            diagnostic.error = Some(e);
+            return diagnostic;
+        }
+    };
+    diagnostic.read = bytes.len();
+    bytes.truncate(match kind {
+        SourceKind::Dmi        => NewlinePolicy::default().trimmed_len(&bytes),
+        SourceKind::DeviceTree => bytes.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1),
+    });
+    diagnostic.trimmed = diagnostic.read - bytes.len();
+    let sensitive = kind == SourceKind::Dmi && DmiField::ALL.iter().any(|field| field.attribute() == file && field.is_root_only());
+    diagnostic.value = Some(if sensitive {
+        let mut hasher = Fnv1a::default();
+        hasher.write(&bytes);
+        DiagnosticValue::Redacted { len: bytes.len(), hash: hasher.finish() }
+    } else {
+        DiagnosticValue::Plain(bytes)
+    });
+    diagnostic
+}
+
+impl DiagnosticReport {
+    /// Renders the report as a JSON object, e.g. for attaching to bug reports by tooling.
+    ///
+    /// The object has a `board` member with the detected board's [JSON form](BoardId::to_json)
+    /// (null if detection failed), an `error` member with the detection error (null if it
+    /// succeeded), and a `files` array of an object per file, with its `path`, `exists`, `mode`,
+    /// `size`, `read`, `trimmed`, `value` and `error` (null where unknown). A plain value is a
+    /// string, converted lossily if it isn't valid UTF-8, and a redacted one is an object of its
+    /// `len` and its `hash` as 16 hexadecimal digits.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{\"board\":");
+        match &self.board {
+            Ok(board) => { json.push_str(&board.to_json()); json.push_str(",\"error\":null") }
+            Err(e) => { json.push_str("null,\"error\":"); push_string(&mut json, &e.to_string()) }
+        }
+        json.push_str(",\"files\":[");
+        for (i, file) in self.files.iter().enumerate() {
+            if i > 0 { json.push(',') }
+            file.push_json(&mut json);
+        }
+        json.push_str("]}");
+        json
+    }
+}
+
+impl FileDiagnostic {
+    /// Pushes the file's object in the [JSON form](DiagnosticReport::to_json) of the report.
+    fn push_json(&self, json: &mut String) {
+        let number = |n: Option<u64>| n.map_or_else(|| "null".to_owned(), |n| n.to_string());
+        json.push_str("{\"path\":");
+        push_string(json, &self.path.to_string_lossy());
+        let _ = write!(
+            json, ",\"exists\":{},\"mode\":{},\"size\":{},\"read\":{},\"trimmed\":{},\"value\":",
+            self.exists, number(self.mode.map(u64::from)), number(self.size), self.read, self.trimmed,
+        );
+        match &self.value {
+            None => json.push_str("null"),
+            Some(DiagnosticValue::Plain(bytes)) => push_string(json, &String::from_utf8_lossy(bytes)),
+            Some(DiagnosticValue::Redacted { len, hash }) => { let _ = write!(json, "{{\"len\":{len},\"hash\":\"{hash:016x}\"}}"); }
+        }
+        json.push_str(",\"error\":");
+        match &self.error {
+            Some(e) => push_string(json, &e.to_string()),
+            None => json.push_str("null"),
+        }
+        json.push('}');
+    }
+}
+
+impl Display for DiagnosticValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Plain(bytes)           => write!(f, "\"{}\"", bytes.escape_ascii()),
+            Self::Redacted { len, hash } => write!(f, "redacted ({len} bytes, hash {hash:016x})"),
+        }
+    }
+}
+
+impl Display for FileDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: ", self.path.display())?;
+        if !self.exists { return write!(f, "missing") }
+        if let Some(mode) = self.mode { write!(f, "mode {mode:04o}, ")? }
+        if let Some(size) = self.size { write!(f, "size {size}, ")? }
+        if let Some(error) = &self.error { return write!(f, "error: {error}") }
+        write!(f, "read {} bytes, trimmed {}", self.read, self.trimmed)?;
+        if let Some(value) = &self.value { write!(f, ", value {value}")? }
+        Ok(())
+    }
+}
+
+impl Display for DiagnosticReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.board {
+            Ok(board) => writeln!(f, "detected: {board}")?,
+            Err(e) => writeln!(f, "detection failed: {e}")?,
+        }
+        for file in &self.files { writeln!(f, "{file}")? }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    #[test]
+    fn report() {
+        let root = TempDir::new("diagnose");
+        root.write("sys/class/dmi/id/board_vendor", "VENDOR\n");
+        root.write("sys/class/dmi/id/board_name", "NAME\r\n");
+        let report = diagnose_at(root.path());
+        assert_eq!(report.board.as_ref().unwrap().name(), Some(&b"NAME"[..]));
+        assert_eq!(report.files.len(), 8);
+        let name = &report.files[1];
+        assert!(name.exists && name.error.is_none());
+        assert_eq!((name.read, name.trimmed), (6, 2));
+        assert_eq!(name.value, Some(DiagnosticValue::Plain(b"NAME".to_vec())));
+        assert!(!report.files[2].exists);
+        assert!(report.files[7].path.ends_with("board_serial"));
+        let report = report.to_string();
+        assert!(report.starts_with("detected: VENDOR NAME\n"));
+        assert!(report.contains("board_name: mode 0"));
+        assert!(report.contains("read 6 bytes, trimmed 2, value \"NAME\"\n"));
+        assert!(report.contains("board_version: missing\n"));
+    }
+
+    #[test]
+    fn redacted() {
+        let root = TempDir::new("diagnose-redacted");
+        root.write("sys/class/dmi/id/board_serial", "SERIAL\n");
+        let report = diagnose_at(root.path());
+        let serial = report.files.last().unwrap();
+        // FNV-1a of "SERIAL", so the hash is comparable across reports
+        assert_eq!(serial.value, Some(DiagnosticValue::Redacted { len: 6, hash: 0xb4c6_bd38_33ce_0a19 }));
+        assert!(!report.to_string().contains("SERIAL"));
+        assert!(!report.to_json().contains("SERIAL"));
+        assert!(report.to_json().ends_with(",\"value\":{\"len\":6,\"hash\":\"b4c6bd3833ce0a19\"},\"error\":null}]}"));
+    }
+
+    #[test]
+    fn json() {
+        let root = TempDir::new("diagnose-json");
+        let path = root.write("sys/class/dmi/id/board_name", "NA\"ME\n");
+        let report = DiagnosticReport { files: vec![diagnose_file(SourceKind::Dmi, "board_name", path.clone())], board: BoardId::detect_at(root.path()) };
+        let mode = report.files[0].mode.map_or("null".to_owned(), |mode| mode.to_string());
+        assert_eq!(report.to_json(), format!(
+            r#"{{"board":{{"vendor":null,"name":"NA\"ME","version":null}},"error":null,"files":[{{"path":{path:?},"exists":true,"mode":{mode},"size":6,"read":6,"trimmed":1,"value":"NA\"ME","error":null}}]}}"#,
+            path = path.to_str().unwrap(),
+        ));
+        let failed = DiagnosticReport { files: Vec::new(), board: Err(io::Error::other("boom")) };
+        assert_eq!(failed.to_json(), r#"{"board":null,"error":"boom","files":[]}"#);
+    }
+}
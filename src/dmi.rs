@@ -0,0 +1,236 @@
+//! DMI attributes, and reading them from sysfs.
+
+#[cfg(feature = "std")] use std::{fs, io, path::Path};
+
+#[cfg(feature = "std")] use crate::{is_placeholder, NewlinePolicy};
+
+/// The sysfs DMI directory, relative to the filesystem root.
+#[cfg(feature = "std")]
+pub(crate) const DIR: &str = "sys/class/dmi/id";
+
+/// A standard DMI attribute, as exposed by Linux in `/sys/class/dmi/id`.
+#[derive(Debug, Hash, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
+pub enum DmiField {
+    /// `bios_vendor`.
+    BiosVendor,
+    /// `bios_version`.
+    BiosVersion,
+    /// `bios_date`.
+    BiosDate,
+    /// `bios_release`.
+    BiosRelease,
+    /// `ec_firmware_release`.
+    EcFirmwareRelease,
+    /// `sys_vendor`.
+    SysVendor,
+    /// `product_name`.
+    ProductName,
+    /// `product_version`.
+    ProductVersion,
+    /// `product_serial`.
+    ProductSerial,
+    /// `product_uuid`.
+    ProductUuid,
+    /// `product_sku`.
+    ProductSku,
+    /// `product_family`.
+    ProductFamily,
+    /// `board_vendor`.
+    BoardVendor,
+    /// `board_name`.
+    BoardName,
+    /// `board_version`.
+    BoardVersion,
+    /// `board_serial`.
+    BoardSerial,
+    /// `board_asset_tag`.
+    BoardAssetTag,
+    /// `chassis_vendor`.
+    ChassisVendor,
+    /// `chassis_type`.
+    ChassisType,
+    /// `chassis_version`.
+    ChassisVersion,
+    /// `chassis_serial`.
+    ChassisSerial,
+    /// `chassis_asset_tag`.
+    ChassisAssetTag,
+}
+
+impl DmiField {
+    /// All the fields, in sysfs listing order.
+    pub const ALL: [Self; 22] = [
+        Self::BiosVendor, Self::BiosVersion, Self::BiosDate, Self::BiosRelease, Self::EcFirmwareRelease,
+        Self::SysVendor, Self::ProductName, Self::ProductVersion, Self::ProductSerial, Self::ProductUuid, Self::ProductSku, Self::ProductFamily,
+        Self::BoardVendor, Self::BoardName, Self::BoardVersion, Self::BoardSerial, Self::BoardAssetTag,
+        Self::ChassisVendor, Self::ChassisType, Self::ChassisVersion, Self::ChassisSerial, Self::ChassisAssetTag,
+    ];
+
+    /// Gets all the fields, in sysfs listing order.
+    ///
+    /// Same as [`Self::ALL`].
+    pub fn all() -> &'static [Self] { &Self::ALL }
+
+    /// Gets the name of the attribute's file.
+    pub const fn attribute(self) -> &'static str {
+        match self {
+            Self::BiosVendor        => "bios_vendor",
+            Self::BiosVersion       => "bios_version",
+            Self::BiosDate          => "bios_date",
+            Self::BiosRelease       => "bios_release",
+            Self::EcFirmwareRelease => "ec_firmware_release",
+            Self::SysVendor         => "sys_vendor",
+            Self::ProductName       => "product_name",
+            Self::ProductVersion    => "product_version",
+            Self::ProductSerial     => "product_serial",
+            Self::ProductUuid       => "product_uuid",
+            Self::ProductSku        => "product_sku",
+            Self::ProductFamily     => "product_family",
+            Self::BoardVendor       => "board_vendor",
+            Self::BoardName         => "board_name",
+            Self::BoardVersion      => "board_version",
+            Self::BoardSerial       => "board_serial",
+            Self::BoardAssetTag     => "board_asset_tag",
+            Self::ChassisVendor     => "chassis_vendor",
+            Self::ChassisType       => "chassis_type",
+            Self::ChassisVersion    => "chassis_version",
+            Self::ChassisSerial     => "chassis_serial",
+            Self::ChassisAssetTag   => "chassis_asset_tag",
+        }
+    }
+
+    /// Checks whether the attribute is typically only readable by root.
+    ///
+    /// The kernel makes the serials and the product UUID readable by root only.
+    pub fn is_root_only(self) -> bool {
+        matches!(self, Self::ProductSerial | Self::ProductUuid | Self::BoardSerial | Self::ChassisSerial)
+    }
+
+    /// Checks whether the attribute identifies the individual machine (serials, the product UUID
+    /// and asset tags), rather than its model, and so should be treated as personal data.
+    ///
+    /// Every [root-only](Self::is_root_only) attribute is sensitive.
+    pub fn is_sensitive(self) -> bool {
+        self.is_root_only() || matches!(self, Self::BoardAssetTag | Self::ChassisAssetTag)
+    }
+}
+
+/// Checks whether reading any of the fields typically requires root (see
+/// [`DmiField::is_root_only`]).
+///
+/// This is a static classification, it does no I/O.
+pub fn requires_root(fields: &[DmiField]) -> bool {
+    fields.iter().any(|field| field.is_root_only())
+}
+
+/// Reads the DMI attribute of the system rooted at `root`, without its trailing newline.
+///
+/// Returns `Ok(None)` if the attribute doesn't exist.
+#[cfg(feature = "std")]
+pub(crate) fn read_attribute(root: &Path, field: DmiField) -> io::Result<Option<Vec<u8>>> {
+    match fs::read(root.join(DIR).join(field.attribute())) {
+        Ok(mut bytes) => {
+            bytes.truncate(NewlinePolicy::default().trimmed_len(&bytes));
+            Ok(Some(bytes))
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Reads every standard DMI attribute that exists and is readable, in [`DmiField::ALL`] order,
+/// without their trailing newline.
+///
+/// Attributes denied to the current user (see [`DmiField::is_root_only`]) are skipped. See
+/// [`detect_inventory_at`].
+#[cfg(feature = "std")]
+pub fn detect_inventory() -> io::Result<Vec<(DmiField, Vec<u8>)>> { detect_inventory_at("/", true) }
+
+/// Reads every standard DMI attribute of the system rooted at the given path that exists, in
+/// [`DmiField::ALL`] order, without their trailing newline.
+///
+/// Attributes denied to the current user are skipped if `skip_denied`, and fail otherwise.
+#[cfg(feature = "std")]
+pub fn detect_inventory_at(root: impl AsRef<Path>, skip_denied: bool) -> io::Result<Vec<(DmiField, Vec<u8>)>> {
+    let mut inventory = Vec::new();
+    for field in DmiField::ALL {
+        match read_attribute(root.as_ref(), field) {
+            Ok(Some(value)) => inventory.push((field, value)),
+            Ok(None) => {}
+            Err(e) if skip_denied && e.kind() == io::ErrorKind::PermissionDenied => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(inventory)
+}
+
+/// Reads the most human-meaningful model name: the `product_name` DMI attribute if it exists and
+/// isn't a [placeholder](is_placeholder), otherwise the `board_name` one.
+///
+/// Laptops tend to have a marketable product name and a cryptic board name, and desktops the
+/// reverse. See [`best_model_at`].
+#[cfg(feature = "std")]
+pub fn best_model() -> io::Result<Option<Vec<u8>>> { best_model_at("/") }
+
+/// Reads the most human-meaningful model name of the system rooted at the given path.
+///
+/// See [`best_model`].
+#[cfg(feature = "std")]
+pub fn best_model_at(root: impl AsRef<Path>) -> io::Result<Option<Vec<u8>>> {
+    match read_attribute(root.as_ref(), DmiField::ProductName)? {
+        Some(product) if !is_placeholder(&product) => Ok(Some(product)),
+        _ => read_attribute(root.as_ref(), DmiField::BoardName),
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    #[test]
+    fn inventory() {
+        let root = TempDir::new("dmi-inventory");
+        root.write("sys/class/dmi/id/board_name", "NAME\n");
+        root.write("sys/class/dmi/id/bios_vendor", "BIOS\r\n");
+        root.write("sys/class/dmi/id/chassis_type", "3\n");
+        assert_eq!(detect_inventory_at(root.path(), false).unwrap(), [
+            (DmiField::BiosVendor , b"BIOS".to_vec()),
+            (DmiField::BoardName  , b"NAME".to_vec()),
+            (DmiField::ChassisType, b"3".to_vec()),
+        ]);
+        assert_eq!(detect_inventory_at(TempDir::new("dmi-inventory-empty").path(), false).unwrap(), []);
+    }
+
+    #[test]
+    fn best_model() {
+        let root = TempDir::new("dmi-best-model");
+        root.write("sys/class/dmi/id/board_name", "20XWCTO1WW\n");
+        assert_eq!(best_model_at(root.path()).unwrap(), Some(b"20XWCTO1WW".to_vec()));
+        root.write("sys/class/dmi/id/product_name", "To be filled by O.E.M.\n");
+        assert_eq!(best_model_at(root.path()).unwrap(), Some(b"20XWCTO1WW".to_vec()), "placeholder product name won");
+        root.write("sys/class/dmi/id/product_name", "ThinkPad T14 Gen 2i\n");
+        assert_eq!(best_model_at(root.path()).unwrap(), Some(b"ThinkPad T14 Gen 2i".to_vec()));
+        assert_eq!(best_model_at(TempDir::new("dmi-best-model-empty").path()).unwrap(), None);
+    }
+
+    #[test]
+    fn root_only() {
+        assert!( requires_root(&[DmiField::BoardSerial]));
+        assert!( requires_root(&[DmiField::BoardVendor, DmiField::ProductUuid]));
+        assert!(!requires_root(&[DmiField::BoardVendor]));
+        assert!(!requires_root(&[]));
+    }
+
+    #[test]
+    fn sensitive() {
+        assert_eq!(DmiField::all().len(), 22);
+        assert!(DmiField::all().windows(2).all(|w| w[0] < w[1]), "duplicated or out of order");
+        let sensitive = DmiField::all().iter().copied().filter(|field| field.is_sensitive()).collect::<Vec<_>>();
+        assert_eq!(sensitive, [
+            DmiField::ProductSerial, DmiField::ProductUuid, DmiField::BoardSerial, DmiField::BoardAssetTag,
+            DmiField::ChassisSerial, DmiField::ChassisAssetTag,
+        ]);
+        assert!(DmiField::all().iter().all(|field| !field.is_root_only() || field.is_sensitive()));
+    }
+}
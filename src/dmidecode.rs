@@ -0,0 +1,127 @@
+//! Parsing [`BoardId`]s out of `dmidecode` text output.
+
+use alloc::vec::Vec;
+
+use crate::{BoardId, CapacityError};
+
+/// The title of the baseboard (DMI type 2) section.
+const TITLE: &str = "Base Board Information";
+
+/// Gets the parts of every baseboard section of `dmidecode` text output, in order.
+fn sections(text: &str) -> impl Iterator<Item = [Option<&str>; 3]> {
+    let mut lines = text.lines().peekable();
+    core::iter::from_fn(move || {
+        lines.by_ref().find(|line| line.trim_end() == TITLE)?;
+        let mut parts = [None; 3];
+        // the section's fields are indented, and it ends at a blank or unindented line
+        while let Some(line) = lines.next_if(|line| line.starts_with([' ', '\t']) && !line.trim().is_empty()) {
+            let Some((key, value)) = line.trim().split_once(':') else { continue };
+            let index = match key { "Manufacturer" => 0, "Product Name" => 1, "Version" => 2, _ => continue };
+            parts[index] = Some(value.trim()).filter(|value| !value.is_empty());
+        }
+        Some(parts)
+    })
+}
+
+impl BoardId {
+    /// Parses the [`BoardId`] out of the first "Base Board Information" section of `dmidecode`
+    /// text output, from its Manufacturer, Product Name and Version fields.
+    ///
+    /// Returns `Ok(None)` if there's no such section. Other sections, other fields, and the fields'
+    /// order and indentation are ignored.
+    pub fn from_dmidecode_text(text: &str) -> Result<Option<Self>, CapacityError> {
+        sections(text).next().map(Self::from_dmidecode_parts).transpose()
+    }
+
+    /// Parses the [`BoardId`]s out of every "Base Board Information" section of `dmidecode` text
+    /// output, in order.
+    ///
+    /// See [`Self::from_dmidecode_text`].
+    pub fn from_dmidecode_text_all(text: &str) -> Result<Vec<Self>, CapacityError> {
+        sections(text).map(Self::from_dmidecode_parts).collect()
+    }
+
+    /// Makes a [`BoardId`] from the parts of a `dmidecode` baseboard section.
+    fn from_dmidecode_parts([vendor, name, version]: [Option<&str>; 3]) -> Result<Self, CapacityError> {
+        Self::from_parts(vendor.map(str::as_bytes), name.map(str::as_bytes), version.map(str::as_bytes))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    /// Output of dmidecode 3.3 (tab-indented).
+    const DMIDECODE_3: &str = "\
+# dmidecode 3.3
+Getting SMBIOS data from sysfs.
+SMBIOS 3.3.0 present.
+
+Handle 0x0001, DMI type 1, 27 bytes
+System Information
+\tManufacturer: System manufacturer
+\tProduct Name: System Product Name
+\tVersion: System Version
+
+Handle 0x0002, DMI type 2, 15 bytes
+Base Board Information
+\tManufacturer: ASUSTeK COMPUTER INC.
+\tProduct Name: PRIME B550-PLUS
+\tVersion: Rev X.0x
+\tSerial Number: 201075725201234
+\tAsset Tag: Default string
+\tFeatures:
+\t\tBoard is a hosting board
+\t\tBoard is replaceable
+\tLocation In Chassis: Default string
+\tChassis Handle: 0x0003
+\tType: Motherboard
+\tContained Object Handles: 0
+
+Handle 0x0003, DMI type 3, 22 bytes
+Chassis Information
+\tManufacturer: Default string
+\tVersion: Default string
+";
+
+    /// Output of dmidecode 2.9 (space-indented, fields reordered), with two baseboards.
+    const DMIDECODE_2: &str = "\
+# dmidecode 2.9
+SMBIOS 2.4 present.
+
+Handle 0x0200, DMI type 2, 9 bytes
+Base Board Information
+        Product Name: 0UW306
+        Manufacturer: Dell Inc.
+        Version:
+        Serial Number: ..CN697027AB0123.
+
+Handle 0x0201, DMI type 2, 9 bytes
+Base Board Information
+        Manufacturer: Dell Inc.
+        Product Name: 0XR032
+        Version: A00
+";
+
+    #[test]
+    fn dmidecode_3() {
+        let board = BoardId::from_dmidecode_text(DMIDECODE_3).unwrap().unwrap();
+        assert_eq!(board, BoardId::from_parts(Some(b"ASUSTeK COMPUTER INC."), Some(b"PRIME B550-PLUS"), Some(b"Rev X.0x")).unwrap());
+    }
+
+    #[test]
+    fn dmidecode_2() {
+        let board = BoardId::from_dmidecode_text(DMIDECODE_2).unwrap().unwrap();
+        assert_eq!(board, BoardId::from_parts(Some(b"Dell Inc."), Some(b"0UW306"), None).unwrap());
+        assert_eq!(BoardId::from_dmidecode_text_all(DMIDECODE_2).unwrap(), [
+            board,
+            BoardId::from_parts(Some(b"Dell Inc."), Some(b"0XR032"), Some(b"A00")).unwrap(),
+        ]);
+    }
+
+    #[test]
+    fn no_baseboard() {
+        assert_eq!(BoardId::from_dmidecode_text("# dmidecode 3.3\nSystem Information\n\tManufacturer: X\n"), Ok(None));
+        assert_eq!(BoardId::from_dmidecode_text_all(""), Ok(Vec::new()));
+    }
+}
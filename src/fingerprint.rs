@@ -0,0 +1,127 @@
+//! [`BoardId::fingerprint`], a hash that's stable across releases and platforms, and the
+//! [canonical byte string](BoardId::canonical_id_bytes) it hashes.
+
+use alloc::{format, string::String, vec::Vec};
+use core::hash::Hasher;
+
+use crate::{BoardId, Part};
+
+/// The separator of the [canonical byte string](BoardId::canonical_id_bytes): the ASCII unit
+/// separator.
+pub const CANONICAL_SEPARATOR: u8 = 0x1f;
+
+/// The 64-bit FNV-1a hasher, whose output, unlike that of the standard library's hashers, is
+/// specified and therefore stable.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self { Self(0xcbf2_9ce4_8422_2325) }
+}
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 { self.0 }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes { self.0 = (self.0 ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3) }
+    }
+
+    // fixed width and endianness, so the hash doesn't depend on the platform
+    fn write_usize(&mut self, i: usize) { self.write(&(i as u64).to_le_bytes()) }
+}
+
+impl BoardId {
+    /// Gets the detected parts joined with the separator, e.g. for hashing or storage without
+    /// going through [`Display`](core::fmt::Display).
+    ///
+    /// Undetected parts are skipped, so which part a field is can't be told apart. See
+    /// [`Self::canonical_id_bytes`] for a positional encoding.
+    pub fn id_bytes(&self, separator: u8) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.version as usize + 2);
+        for (i, part) in Part::ALL.into_iter().filter_map(|part| self.part(part)).enumerate() {
+            if i > 0 { bytes.push(separator) }
+            bytes.extend_from_slice(part);
+        }
+        bytes
+    }
+
+    /// Gets the canonical byte string of the [`BoardId`]: the vendor, name and version, each empty
+    /// if undetected, separated by [`CANONICAL_SEPARATOR`] (`\x1f`).
+    ///
+    /// It's guaranteed to be stable across releases, so digests computed over it don't shift, and
+    /// the [fingerprint](Self::fingerprint) is computed over it. It's unambiguous as long as no
+    /// part contains the separator, which DMI strings don't.
+    pub fn canonical_id_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.version as usize + 2);
+        for (i, part) in Part::ALL.into_iter().enumerate() {
+            if i > 0 { bytes.push(CANONICAL_SEPARATOR) }
+            bytes.extend_from_slice(self.part(part).unwrap_or_default());
+        }
+        bytes
+    }
+
+    /// Gets a fingerprint of the [`BoardId`]'s parts.
+    ///
+    /// It's the 64-bit FNV-1a hash of the [canonical byte string](Self::canonical_id_bytes), so
+    /// it's stable across releases and platforms. It's not cryptographic.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv1a::default();
+        hasher.write(&self.canonical_id_bytes());
+        hasher.finish()
+    }
+
+    /// Gets a strong HTTP entity tag for the [`BoardId`], derived from its
+    /// [fingerprint](Self::fingerprint), quotes included, e.g. `"bid-0123456789abcdef"`.
+    ///
+    /// Like the fingerprint, it's stable across runs, releases and platforms, so it can be compared
+    /// against `If-None-Match` directly.
+    pub fn etag(&self) -> String { format!("\"bid-{:016x}\"", self.fingerprint()) }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fnv1a() {
+        // the reference test vectors
+        let hash = |bytes: &[u8]| { let mut hasher = Fnv1a::default(); hasher.write(bytes); hasher.finish() };
+        assert_eq!(hash(b""), 0xcbf29ce484222325);
+        assert_eq!(hash(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(hash(b"foobar"), 0x85944171f73967e8);
+    }
+
+    #[test]
+    fn fingerprint() {
+        let board = BoardId::from_parts(Some(b"VENDOR"), Some(b"NAME"), None).unwrap();
+        assert_eq!(board.fingerprint(), BoardId::from_streams(Some("VENDOR\n".as_bytes()), Some("NAME\n".as_bytes()), None::<&[u8]>).unwrap().fingerprint());
+        assert_ne!(board.fingerprint(), BoardId::from_parts(Some(b"VENDORN"), Some(b"AME"), None).unwrap().fingerprint());
+    }
+
+    #[test]
+    fn id_bytes() {
+        let board = BoardId::from_parts(Some(b"VENDOR"), None, Some(b"VERSION")).unwrap();
+        assert_eq!(board.id_bytes(b'|'), b"VENDOR|VERSION");
+        assert_eq!(board.canonical_id_bytes(), b"VENDOR\x1f\x1fVERSION");
+        let undetected = BoardId::from_parts(None, None, None).unwrap();
+        assert_eq!(undetected.id_bytes(b'|'), b"");
+        assert_eq!(undetected.canonical_id_bytes(), b"\x1f\x1f");
+    }
+
+    #[test]
+    fn canonical_fingerprint() {
+        let board = BoardId::from_parts(Some(b"VENDOR"), Some(b"NAME"), None).unwrap();
+        let mut hasher = Fnv1a::default();
+        hasher.write(b"VENDOR\x1fNAME\x1f");
+        assert_eq!(board.fingerprint(), hasher.finish());
+    }
+
+    #[test]
+    fn etag() {
+        let board = BoardId::from_parts(Some(b"VENDOR"), Some(b"NAME"), None).unwrap();
+        let etag = board.etag();
+        assert_eq!(etag, "\"bid-20f0b55c7d7fda80\"", "must be stable across runs");
+        assert_eq!(etag, format!("\"bid-{:016x}\"", board.fingerprint()));
+        assert!(etag.len() == 22 && etag[5..21].bytes().all(|b| b.is_ascii_hexdigit()));
+    }
+}
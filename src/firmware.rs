@@ -0,0 +1,81 @@
+//! [`firmware_flags`], the firmware's security-relevant flags, to report alongside the
+//! [`BoardId`](crate::BoardId).
+
+use std::{fs, io, path::Path};
+
+/// The EFI directory, relative to the filesystem root. It only exists when booted with UEFI.
+const EFI_DIR: &str = "sys/firmware/efi";
+
+/// The `SecureBoot` EFI variable (of the EFI global variable GUID), relative to the filesystem
+/// root.
+const SECURE_BOOT: &str = "sys/firmware/efi/efivars/SecureBoot-8be4df61-93ca-11d2-aa0d-00e098032b8c";
+
+/// Whether secure boot is enabled.
+#[derive(Debug, Default, Hash, Clone, Copy, PartialEq, Eq)]
+pub enum SecureBoot {
+    /// Secure boot is enabled.
+    Enabled,
+    /// Secure boot is disabled.
+    Disabled,
+    /// The state is unknown, e.g. because the system wasn't booted with UEFI.
+    #[default]
+    Unknown,
+}
+
+/// The firmware's security-relevant flags.
+#[derive(Debug, Default, Hash, Clone, Copy, PartialEq, Eq)]
+pub struct FirmwareFlags {
+    /// Whether the system was booted with UEFI (rather than a legacy BIOS).
+    pub uefi: bool,
+    /// Whether secure boot is enabled.
+    pub secure_boot: SecureBoot,
+}
+
+/// Reads the firmware's flags.
+///
+/// See [`firmware_flags_at`].
+pub fn firmware_flags() -> io::Result<FirmwareFlags> { firmware_flags_at("/") }
+
+/// Reads the firmware's flags of the system whose filesystem is rooted at the given path.
+///
+/// The secure boot state is read from the `SecureBoot` variable in `sys/firmware/efi/efivars`: its
+/// 4 attribute bytes followed by a 1 (enabled) or 0 (disabled) byte. It's
+/// [`SecureBoot::Unknown`] if the variable is absent or malformed.
+pub fn firmware_flags_at(root: impl AsRef<Path>) -> io::Result<FirmwareFlags> {
+    let root = root.as_ref();
+    let secure_boot = match fs::read(root.join(SECURE_BOOT)) {
+        Ok(variable) => match variable.as_slice() {
+            [_, _, _, _, 1] => SecureBoot::Enabled,
+            [_, _, _, _, 0] => SecureBoot::Disabled,
+            _ => SecureBoot::Unknown,
+        },
+        Err(e) if e.kind() == io::ErrorKind::NotFound => SecureBoot::Unknown,
+        Err(e) => return Err(e),
+    };
+    Ok(FirmwareFlags { uefi: root.join(EFI_DIR).is_dir(), secure_boot })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    #[test]
+    fn secure_boot() {
+        let root = TempDir::new("firmware-secure-boot");
+        root.write(SECURE_BOOT, b"\x06\x00\x00\x00\x01");
+        assert_eq!(firmware_flags_at(root.path()).unwrap(), FirmwareFlags { uefi: true, secure_boot: SecureBoot::Enabled });
+        root.write(SECURE_BOOT, b"\x06\x00\x00\x00\x00");
+        assert_eq!(firmware_flags_at(root.path()).unwrap(), FirmwareFlags { uefi: true, secure_boot: SecureBoot::Disabled });
+        root.write(SECURE_BOOT, b"\x06\x00\x00\x00");
+        assert_eq!(firmware_flags_at(root.path()).unwrap().secure_boot, SecureBoot::Unknown, "truncated variable");
+    }
+
+    #[test]
+    fn absent() {
+        let root = TempDir::new("firmware-absent");
+        assert_eq!(firmware_flags_at(root.path()).unwrap(), FirmwareFlags { uefi: false, secure_boot: SecureBoot::Unknown });
+        root.write("sys/firmware/efi/efivars/BootCurrent-8be4df61-93ca-11d2-aa0d-00e098032b8c", b"\x06\x00\x00\x00\x01\x00");
+        assert_eq!(firmware_flags_at(root.path()).unwrap(), FirmwareFlags { uefi: true, secure_boot: SecureBoot::Unknown });
+    }
+}
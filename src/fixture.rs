@@ -0,0 +1,48 @@
+//! Temporary fixture directories and boards for tests.
+
+use std::{fs, path::{Path, PathBuf}};
+
+use crate::BoardId;
+
+/// A temporary directory that's removed when dropped.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Creates a fresh, empty temporary directory unique to the test `name`.
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("board_id-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+
+    /// Creates a fresh temporary root, as per [`Self::new`], whose DMI has the board vendor
+    /// "VENDOR" and name "NAME".
+    pub fn with_board(name: &str) -> Self {
+        let root = Self::new(name);
+        root.write("sys/class/dmi/id/board_vendor", "VENDOR\n");
+        root.write("sys/class/dmi/id/board_name", "NAME\n");
+        root
+    }
+
+    /// Gets the directory's path.
+    pub fn path(&self) -> &Path { &self.0 }
+
+    /// Writes a file at the `relative` path, creating parent directories as needed.
+    pub fn write(&self, relative: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> PathBuf {
+        let path = self.0.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, contents).unwrap();
+        path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) { let _ = fs::remove_dir_all(&self.0); }
+}
+
+/// Makes a [`BoardId`] of the parts, where empty parts are undetected.
+pub fn board(vendor: &str, name: &str, version: &str) -> BoardId {
+    fn part(part: &str) -> Option<&[u8]> { Some(part.as_bytes()).filter(|part| !part.is_empty()) }
+    BoardId::from_parts(part(vendor), part(name), part(version)).unwrap()
+}
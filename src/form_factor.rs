@@ -0,0 +1,171 @@
+//! [`FormFactor`] classification from DMI data.
+
+use std::{io, path::Path};
+
+use crate::{detect_inventory_at, server::SERVER_CHASSIS_TYPES, DmiField};
+
+/// The form factor of a machine.
+#[derive(Debug, Hash, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
+pub enum FormFactor {
+    /// A laptop, notebook, convertible or tablet.
+    Laptop,
+    /// A desktop, tower or all-in-one.
+    Desktop,
+    /// A server, rack mount or blade.
+    Server,
+    /// A mini PC or stick PC.
+    MiniPc,
+    /// A virtual machine.
+    Vm,
+    /// An embedded PC or IoT gateway.
+    Embedded,
+    /// Unknown.
+    Unknown,
+}
+
+/// How confident a [`FormFactor`] classification is.
+#[derive(Debug, Hash, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
+pub enum Confidence {
+    /// Nothing indicated the form factor.
+    Low,
+    /// A vendor / product name heuristic indicated the form factor.
+    Medium,
+    /// The chassis type or a hypervisor's signature indicated the form factor.
+    High,
+}
+
+/// Signatures of hypervisors: a DMI field and a substring of its value.
+const VM_SIGNATURES: &[(DmiField, &str)] = &[
+    (DmiField::SysVendor  , "QEMU"),
+    (DmiField::SysVendor  , "VMware"),
+    (DmiField::SysVendor  , "innotek GmbH"),
+    (DmiField::SysVendor  , "Xen"),
+    (DmiField::SysVendor  , "Parallels"),
+    (DmiField::SysVendor  , "Amazon EC2"),
+    (DmiField::SysVendor  , "OpenStack"),
+    // not the "Google" system vendor, which Chromebooks report too
+    (DmiField::ProductName, "Google Compute Engine"),
+    (DmiField::ProductName, "Virtual Machine"),
+    (DmiField::ProductName, "VirtualBox"),
+    (DmiField::ProductName, "KVM"),
+    (DmiField::ProductName, "Bochs"),
+    (DmiField::BiosVendor , "SeaBIOS"),
+];
+
+/// The SMBIOS chassis types of each form factor.
+const CHASSIS_TYPES: &[(FormFactor, &[u8])] = &[
+    // Portable, Laptop, Notebook, Hand Held, Sub Notebook, Tablet, Convertible, Detachable
+    (FormFactor::Laptop  , &[8, 9, 10, 11, 14, 30, 31, 32]),
+    // Desktop, Low Profile Desktop, Pizza Box, Mini Tower, Tower, All in One, Sealed-case PC
+    (FormFactor::Desktop , &[3, 4, 5, 6, 7, 13, 24]),
+    (FormFactor::Server  , &SERVER_CHASSIS_TYPES),
+    // Space-saving, Mini PC, Stick PC
+    (FormFactor::MiniPc  , &[15, 35, 36]),
+    // IoT Gateway, Embedded PC
+    (FormFactor::Embedded, &[33, 34]),
+];
+
+/// Heuristics for when the chassis type doesn't tell: a DMI field, a substring of its value, and
+/// the form factor it indicates.
+const HEURISTICS: &[(DmiField, &str, FormFactor)] = &[
+    (DmiField::ProductName   , "NUC"     , FormFactor::MiniPc),
+    (DmiField::BoardName     , "NUC"     , FormFactor::MiniPc),
+    (DmiField::ProductVersion, "ThinkPad", FormFactor::Laptop),
+    (DmiField::ProductFamily , "ThinkPad", FormFactor::Laptop),
+    (DmiField::ProductName   , "Laptop"  , FormFactor::Laptop),
+    (DmiField::ProductName   , "Notebook", FormFactor::Laptop),
+    (DmiField::ProductName   , "PowerEdge", FormFactor::Server),
+    (DmiField::ProductName   , "ProLiant", FormFactor::Server),
+    (DmiField::ProductName   , "OptiPlex", FormFactor::Desktop),
+];
+
+impl FormFactor {
+    /// Classifies the form factor of a machine from its available DMI fields (e.g. as per
+    /// [`detect_inventory`](crate::detect_inventory)), with how confident the classification is.
+    ///
+    /// A [hypervisor signature](VM_SIGNATURES) makes it a [`FormFactor::Vm`], overriding the
+    /// rest. Otherwise the chassis type decides, and when it doesn't, the vendor / product name
+    /// heuristics do. Missing fields are fine.
+    pub fn classify(fields: &[(DmiField, Vec<u8>)]) -> (Self, Confidence) {
+        let has = |field: DmiField, needle: &str| fields.iter().any(|(f, value)| {
+            *f == field && value.windows(needle.len()).any(|window| window == needle.as_bytes())
+        });
+        if VM_SIGNATURES.iter().any(|&(field, needle)| has(field, needle)) { return (Self::Vm, Confidence::High) }
+
+        let chassis_type = fields.iter()
+            .find(|(field, _)| *field == DmiField::ChassisType)
+            .and_then(|(_, value)| std::str::from_utf8(value).ok()?.trim().parse::<u8>().ok())
+            .map(|t| t & 0x7F);
+        if let Some(&(form_factor, _)) = chassis_type.and_then(|t| CHASSIS_TYPES.iter().find(|(_, types)| types.contains(&t))) {
+            return (form_factor, Confidence::High)
+        }
+
+        match HEURISTICS.iter().find(|&&(field, needle, _)| has(field, needle)) {
+            Some(&(_, _, form_factor)) => (form_factor, Confidence::Medium),
+            None => (Self::Unknown, Confidence::Low),
+        }
+    }
+
+    /// Detects the form factor of the machine, with how confident the classification is.
+    ///
+    /// See [`Self::classify`].
+    pub fn detect() -> io::Result<(Self, Confidence)> { Self::detect_at("/") }
+
+    /// Detects the form factor of the system whose filesystem is rooted at the given path, with how
+    /// confident the classification is.
+    ///
+    /// See [`Self::classify`].
+    pub fn detect_at(root: impl AsRef<Path>) -> io::Result<(Self, Confidence)> {
+        Ok(Self::classify(&detect_inventory_at(root, true)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    fn classify(fields: &[(DmiField, &str)]) -> (FormFactor, Confidence) {
+        FormFactor::classify(&fields.iter().map(|&(field, value)| (field, value.as_bytes().to_vec())).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn chassis_type() {
+        let laptop = [(DmiField::SysVendor, "LENOVO"), (DmiField::ProductName, "20XWCTO1WW"), (DmiField::ChassisType, "10")];
+        let desktop = [(DmiField::SysVendor, "ASUS"), (DmiField::BoardName, "PRIME B550-PLUS"), (DmiField::ChassisType, "3")];
+        let server = [(DmiField::SysVendor, "Supermicro"), (DmiField::ProductName, "SYS-5019C-M"), (DmiField::ChassisType, "23")];
+        let mini_pc = [(DmiField::SysVendor, "Intel(R) Client Systems"), (DmiField::ProductName, "NUC11PAHi5"), (DmiField::ChassisType, "35")];
+        let embedded = [(DmiField::SysVendor, "Advantech"), (DmiField::ProductName, "UNO-2271G"), (DmiField::ChassisType, "34")];
+        let chromebook = [(DmiField::SysVendor, "Google"), (DmiField::ProductName, "Lazor"), (DmiField::ChassisType, "9")];
+        assert_eq!(classify(&laptop), (FormFactor::Laptop, Confidence::High));
+        assert_eq!(classify(&desktop), (FormFactor::Desktop, Confidence::High));
+        assert_eq!(classify(&server), (FormFactor::Server, Confidence::High));
+        assert_eq!(classify(&mini_pc), (FormFactor::MiniPc, Confidence::High));
+        assert_eq!(classify(&embedded), (FormFactor::Embedded, Confidence::High));
+        assert_eq!(classify(&chromebook), (FormFactor::Laptop, Confidence::High));
+    }
+
+    #[test]
+    fn vm_overrides() {
+        let qemu = [(DmiField::SysVendor, "QEMU"), (DmiField::ProductName, "Standard PC (Q35 + ICH9, 2009)"), (DmiField::ChassisType, "1")];
+        let hyper_v = [(DmiField::SysVendor, "Microsoft Corporation"), (DmiField::ProductName, "Virtual Machine"), (DmiField::ChassisType, "3")];
+        let virtualbox = [(DmiField::SysVendor, "innotek GmbH"), (DmiField::ProductName, "VirtualBox"), (DmiField::ChassisType, "1")];
+        let gce = [(DmiField::SysVendor, "Google"), (DmiField::ProductName, "Google Compute Engine"), (DmiField::ChassisType, "1")];
+        for vm in [&qemu, &hyper_v, &virtualbox, &gce] { assert_eq!(classify(vm), (FormFactor::Vm, Confidence::High)) }
+    }
+
+    #[test]
+    fn heuristics() {
+        assert_eq!(classify(&[(DmiField::ProductName, "NUC8i5BEH"), (DmiField::ChassisType, "2")]), (FormFactor::MiniPc, Confidence::Medium));
+        assert_eq!(classify(&[(DmiField::ProductVersion, "ThinkPad T14 Gen 2i")]), (FormFactor::Laptop, Confidence::Medium));
+        assert_eq!(classify(&[(DmiField::ChassisType, "2")]), (FormFactor::Unknown, Confidence::Low));
+        assert_eq!(classify(&[]), (FormFactor::Unknown, Confidence::Low));
+    }
+
+    #[test]
+    fn detect_at() {
+        let root = TempDir::new("form-factor");
+        root.write("sys/class/dmi/id/chassis_type", "9\n");
+        assert_eq!(FormFactor::detect_at(root.path()).unwrap(), (FormFactor::Laptop, Confidence::High));
+    }
+}
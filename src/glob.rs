@@ -0,0 +1,124 @@
+//! Matching [`BoardId`]s against glob patterns.
+
+use alloc::vec::Vec;
+#[cfg(feature = "std")] use std::{io, str::FromStr};
+
+use crate::BoardId;
+
+/// Checks whether the text matches the glob pattern.
+///
+/// In the pattern, `*` matches any (possibly empty) run of bytes, `?` matches any single byte, and
+/// every other byte matches itself, case-sensitively.
+pub fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // The position of the last `*` in the pattern and of the text it's matched up to.
+    let mut backtrack = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == b'?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, t));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+/// A rule matching a [`BoardId`] by [glob patterns](glob_match) on its parts.
+///
+/// An undetected part is matched as the empty string, and a part without a pattern matches
+/// anything.
+#[derive(Debug, Default, Hash, Clone, PartialEq, Eq)]
+pub struct Rule {
+    /// The vendor pattern.
+    pub vendor: Option<Vec<u8>>,
+    /// The name pattern.
+    pub name: Option<Vec<u8>>,
+    /// The version pattern.
+    pub version: Option<Vec<u8>>,
+}
+
+impl Rule {
+    /// Checks whether the [`BoardId`] matches the rule.
+    pub fn matches(&self, board: &BoardId) -> bool {
+        [(&self.vendor, board.vendor()), (&self.name, board.name()), (&self.version, board.version())]
+            .into_iter()
+            .all(|(pattern, part)| pattern.as_ref().is_none_or(|pattern| glob_match(pattern, part.unwrap_or_default())))
+    }
+}
+
+/// Parses a rule from the form `VENDOR|NAME|VERSION`.
+///
+/// Trailing fields may be omitted, and an empty field has no pattern, e.g. `ASUS*` and `|*B550*`.
+/// Fails with [`io::ErrorKind::InvalidData`] if there are more than three fields.
+#[cfg(feature = "std")]
+impl FromStr for Rule {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.split('|').map(|field| (!field.is_empty()).then(|| field.as_bytes().to_vec()));
+        let rule = Self {
+             vendor: fields.next().flatten(),
+               name: fields.next().flatten(),
+            version: fields.next().flatten(),
+        };
+        if fields.next().is_some() { return Err(io::Error::new(io::ErrorKind::InvalidData, "a rule has at most three fields")) }
+        Ok(rule)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn glob() {
+        for (pattern, text, expected) in [
+            ("ASUS*"   , "ASUSTeK COMPUTER INC.", true ),
+            ("*B550*"  , "ROG STRIX B550-F"     , true ),
+            ("*B550*"  , "ROG STRIX X570-F"     , false),
+            ("B550"    , "B550"                 , true ),
+            ("B550"    , "B550M"                , false),
+            ("B5?0"    , "B550"                 , true ),
+            ("B5?0"    , "B50"                  , false),
+            ("*"       , ""                     , true ),
+            (""        , ""                     , true ),
+            (""        , "A"                    , false),
+            ("a*b*c"   , "aXbYbZc"              , true ),
+            ("a*b*c"   , "aXbYbZ"               , false),
+            ("asus*"   , "ASUS"                 , false),
+        ] {
+            assert_eq!(glob_match(pattern.as_bytes(), text.as_bytes()), expected, "{pattern:?} ~ {text:?}");
+        }
+    }
+
+    #[test]
+    fn rule_matches() {
+        let board = BoardId::from_parts(Some(b"ASUSTeK COMPUTER INC."), Some(b"ROG STRIX B550-F"), None).unwrap();
+        assert!( "ASUS*|*B550*".parse::<Rule>().unwrap().matches(&board));
+        assert!( "|*B550*"     .parse::<Rule>().unwrap().matches(&board));
+        assert!( ""            .parse::<Rule>().unwrap().matches(&board));
+        assert!( "ASUS*||*"    .parse::<Rule>().unwrap().matches(&board));
+        assert!(!"ASUS*|*X570*".parse::<Rule>().unwrap().matches(&board));
+        assert!(!"||?*"        .parse::<Rule>().unwrap().matches(&board));
+    }
+
+    #[test]
+    fn rule_parse() {
+        assert_eq!("A|B|C".parse::<Rule>().unwrap(), Rule { vendor: Some(b"A".to_vec()), name: Some(b"B".to_vec()), version: Some(b"C".to_vec()) });
+        assert_eq!("|B".parse::<Rule>().unwrap(), Rule { name: Some(b"B".to_vec()), ..Rule::default() });
+        assert_eq!("A|B|C|D".parse::<Rule>().unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
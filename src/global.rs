@@ -0,0 +1,57 @@
+//! The process-global [`BoardId`].
+
+use std::{io, sync::OnceLock};
+
+use crate::BoardId;
+
+/// The process-global [`BoardId`].
+static GLOBAL: OnceLock<BoardId> = OnceLock::new();
+
+impl BoardId {
+    /// Gets the process-global [`BoardId`], detecting it on first use.
+    ///
+    /// If detection fails, the error is returned and detection is attempted again on the next call.
+    pub fn cached() -> io::Result<&'static BoardId> {
+        if let Some(board) = GLOBAL.get() { return Ok(board) }
+        let detected = Self::detect()?;
+        Ok(GLOBAL.get_or_init(|| detected))
+    }
+}
+
+/// Gets the process-global [`BoardId`] (see [`BoardId::cached`]), or `None` if detection failed or
+/// detected nothing.
+///
+/// This suits log contexts and the like, where there's no error to plumb.
+pub fn get() -> Option<&'static BoardId> {
+    BoardId::cached().ok().filter(|board| !board.is_undetected())
+}
+
+/// Gets the process-global [`BoardId`], initializing it with `f` rather than detection if it's not
+/// yet initialized.
+///
+/// This lets embedders seed the global from their own source (e.g. a cache file) before anything
+/// reads it.
+pub fn get_or_init_with(f: impl FnOnce() -> BoardId) -> &'static BoardId {
+    GLOBAL.get_or_init(f)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // The global is shared by the whole test binary, so it's exercised by this single test.
+    #[test]
+    fn global() {
+        let seed = BoardId::from_parts(Some(b"SEED"), None, None).unwrap();
+        let board = get_or_init_with(|| seed);
+        assert_eq!(*board, seed, "something initialized the global before this test");
+        assert!(std::ptr::eq(get().unwrap(), board));
+        assert!(std::ptr::eq(BoardId::cached().unwrap(), board));
+        assert_eq!(*get_or_init_with(|| unreachable!()), seed);
+
+        let first = BoardId::leaked().unwrap();
+        let second = BoardId::leaked().unwrap();
+        assert!(std::ptr::eq(first, second));
+        assert_eq!(first.to_board_id().unwrap(), seed);
+    }
+}
@@ -0,0 +1,100 @@
+//! [`BoardId::group_key`], for bucketing machines by their effective model.
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{BoardId, Part};
+
+/// The knobs of [`BoardId::group_key_with`].
+#[derive(Debug, Default, Hash, Clone, Copy, PartialEq, Eq)]
+pub struct GroupKeyOptions {
+    /// Whether to include the version. Off by default.
+    pub include_version: bool,
+    /// Whether to keep a trailing revision in the name (e.g. "Rev 1.xx"). Off by default.
+    pub keep_revision: bool,
+}
+
+/// Normalizes a part for a group key: lowercased, with runs of whitespace collapsed into a space,
+/// and trimmed.
+pub(crate) fn normalize(part: &[u8]) -> String {
+    String::from_utf8_lossy(part).split_whitespace().map(str::to_lowercase).collect::<Vec<_>>().join(" ")
+}
+
+/// Strips a trailing revision (e.g. " rev 1.xx", "-rev1.0", " (rev. 1.1)") off a normalized name.
+fn strip_revision(name: &str) -> &str {
+    const SEPARATORS: &[char] = &[' ', '-', '_', '/', '('];
+    let revision = name.rmatch_indices("rev").find(|&(i, _)| {
+        let starts_token = name[..i].ends_with(SEPARATORS);
+        let rest = &name[i + 3..];
+        let versionlike = rest.chars().all(|c| c.is_ascii_alphanumeric() || " .:)".contains(c))
+            && rest.trim_start_matches([' ', '.', ':']).starts_with(|c: char| c.is_ascii_digit() || c == 'x');
+        starts_token && versionlike
+    });
+    match revision {
+        Some((i, _)) => name[..i].trim_end_matches(SEPARATORS),
+        None => name,
+    }
+}
+
+impl BoardId {
+    /// Gets the key of the board's effective model, for bucketing machines, e.g. in fleet
+    /// dashboards.
+    ///
+    /// The recipe is stable, so aggregates keyed by it stay comparable across releases: the
+    /// [canonical](Self::part_canonical) vendor and name are each lowercased, their whitespace
+    /// runs are collapsed into a space and trimmed, a trailing revision (e.g. "Rev 1.xx") is
+    /// stripped off the name, and the two are joined by `|`. The version is ignored, and
+    /// undetected parts are empty.
+    ///
+    /// See [`Self::group_key_with`] to include the version or keep the revision.
+    pub fn group_key(&self) -> String { self.group_key_with(GroupKeyOptions::default()) }
+
+    /// Gets the key of the board's effective model, as per [`Self::group_key`] but with the given
+    /// options.
+    ///
+    /// The version, when included, is normalized like the other parts and joined by another `|`.
+    pub fn group_key_with(&self, options: GroupKeyOptions) -> String {
+        let part = |part| self.part_canonical(part).map(normalize).unwrap_or_default();
+        let name = part(Part::Name);
+        let name = if options.keep_revision { &name } else { strip_revision(&name) };
+        let mut key = part(Part::Vendor);
+        key.push('|');
+        key.push_str(name);
+        if options.include_version {
+            key.push('|');
+            key.push_str(&part(Part::Version));
+        }
+        key
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+    use crate::fixture::board;
+
+    #[test]
+    fn golden() {
+        let cases: [((&str, &str, &str), &str); 9] = [
+            (("ASUSTeK COMPUTER INC.", "PRIME B550-PLUS", "Rev X.0x"), "asustek computer inc.|prime b550-plus"),
+            (("ASUSTeK COMPUTER INC. ", " PRIME  B550-PLUS\t", "Rev 1.xx"), "asustek computer inc.|prime b550-plus"),
+            (("Gigabyte Technology Co., Ltd.", "GA-Z77X-UD3H (rev. 1.1)", "x.x"), "gigabyte technology co., ltd.|ga-z77x-ud3h"),
+            (("Micro-Star International Co., Ltd.", "MAG B650 TOMAHAWK WIFI (MS-7D75)", "1.0"), "micro-star international co., ltd.|mag b650 tomahawk wifi (ms-7d75)"),
+            (("Dell Inc.", "0XR032", "A00"), "dell inc.|0xr032"),
+            (("To be filled by O.E.M.", "H110M-R2.0", "Default string"), "|h110m-r2.0"),
+            (("LENOVO", "Revolution Board", "SDK0J40697 WIN"), "lenovo|revolution board"),
+            (("Supermicro", "X11SSH-F", ""), "supermicro|x11ssh-f"),
+            (("", "", ""), "|"),
+        ];
+        for ((vendor, name, version), key) in cases {
+            assert_eq!(board(vendor, name, version).group_key(), key, "{vendor:?} {name:?} {version:?}");
+        }
+    }
+
+    #[test]
+    fn options() {
+        let board = board("ASUSTeK COMPUTER INC.", "PRIME B550-PLUS Rev 1.xx", "Rev X.0x");
+        assert_eq!(board.group_key(), "asustek computer inc.|prime b550-plus");
+        assert_eq!(board.group_key_with(GroupKeyOptions { keep_revision: true, ..Default::default() }), "asustek computer inc.|prime b550-plus rev 1.xx");
+        assert_eq!(board.group_key_with(GroupKeyOptions { include_version: true, ..Default::default() }), "asustek computer inc.|prime b550-plus|rev x.0x");
+    }
+}
@@ -0,0 +1,159 @@
+//! [`HardwareId`], the board, product and chassis identities together.
+
+use std::{fmt::{self, Display}, fs::File, io::{self, Read}, path::Path};
+
+use crate::{dmi, fmt_parts, open_existing_file, BoardId, DmiField};
+
+/// Opens the DMI attributes of the system rooted at the given path.
+fn open_attributes<const N: usize>(root: &Path, fields: [DmiField; N]) -> io::Result<[Option<File>; N]> {
+    let dir = root.join(dmi::DIR);
+    let mut files = fields.map(|_| None);
+    for (file, field) in files.iter_mut().zip(fields) { *file = open_existing_file(dir.join(field.attribute()))? }
+    Ok(files)
+}
+
+/// The identity of the product (system), from the `sys_vendor`, `product_name` and
+/// `product_version` DMI attributes.
+///
+/// On prebuilt machines it's the model (e.g. a laptop's), which the board's often not.
+#[derive(Debug, Hash, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
+pub struct ProductId(BoardId);
+
+impl ProductId {
+    /// Attempts to make a [`ProductId`] from streams of its parts, trimmed like
+    /// [`BoardId`]'s.
+    pub fn from_streams(vendor: Option<impl Read>, name: Option<impl Read>, version: Option<impl Read>) -> io::Result<Self> {
+        BoardId::from_streams(vendor, name, version).map(Self)
+    }
+
+    /// Gets the product's vendor.
+    #[inline]
+    pub fn vendor(&self) -> Option<&[u8]> { self.0.vendor() }
+
+    /// Gets the product's name.
+    #[inline]
+    pub fn name(&self) -> Option<&[u8]> { self.0.name() }
+
+    /// Gets the product's version.
+    #[inline]
+    pub fn version(&self) -> Option<&[u8]> { self.0.version() }
+}
+
+/// Same as [`BoardId`]'s [`Display`] implementation, but for a product.
+impl Display for ProductId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.vendor(), self.name()) {
+            (None, None) => write!(f, "undetected product"),
+            (vendor, None) => write!(f, "{} product", vendor.unwrap_or_default().escape_ascii()),
+            (vendor, name) => fmt_parts(f, vendor, name, self.version()),
+        }
+    }
+}
+
+/// The chassis information, from the `chassis_vendor`, `chassis_type` and `chassis_version` DMI
+/// attributes.
+#[derive(Debug, Default, Hash, Clone, PartialEq, Eq)]
+pub struct ChassisInfo {
+    /// The chassis' vendor.
+    pub vendor: Option<Vec<u8>>,
+    /// The SMBIOS chassis type (e.g. 3 for desktop, 10 for notebook), without the lock bit.
+    pub chassis_type: Option<u8>,
+    /// The chassis' version.
+    pub version: Option<Vec<u8>>,
+}
+
+impl ChassisInfo {
+    /// Attempts to make a [`ChassisInfo`] from streams of its attributes, trimmed like
+    /// [`BoardId`]'s parts.
+    ///
+    /// A chassis type that isn't a decimal number is treated as undetected.
+    pub fn from_streams(vendor: Option<impl Read>, chassis_type: Option<impl Read>, version: Option<impl Read>) -> io::Result<Self> {
+        let parts = BoardId::from_streams(vendor, chassis_type, version)?;
+        Ok(Self {
+            vendor: parts.vendor().map(<[u8]>::to_vec),
+            chassis_type: parts.name().and_then(|t| std::str::from_utf8(t).ok()?.parse::<u8>().ok()).map(|t| t & 0x7F),
+            version: parts.version().map(<[u8]>::to_vec),
+        })
+    }
+}
+
+impl Display for ChassisInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.vendor {
+            Some(vendor) => write!(f, "{} chassis", vendor.escape_ascii())?,
+            None => write!(f, "chassis")?,
+        }
+        match self.chassis_type {
+            Some(chassis_type) => write!(f, " of type {chassis_type}"),
+            None => write!(f, " of undetected type"),
+        }
+    }
+}
+
+/// The board, product and chassis identities of a system together.
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub struct HardwareId {
+    /// The board.
+    pub board: BoardId,
+    /// The product.
+    pub product: ProductId,
+    /// The chassis.
+    pub chassis: ChassisInfo,
+}
+
+impl HardwareId {
+    /// Attempts to detect the [`HardwareId`].
+    ///
+    /// See [`Self::detect_at`].
+    pub fn detect() -> io::Result<Self> { Self::detect_at("/") }
+
+    /// Attempts to detect the [`HardwareId`] of the system whose filesystem is rooted at the given
+    /// path.
+    ///
+    /// The board is detected as per [`BoardId::detect_at`], and the product and chassis from DMI.
+    pub fn detect_at(root: impl AsRef<Path>) -> io::Result<Self> {
+        let root = root.as_ref();
+        let [vendor, name, version] = open_attributes(root, [DmiField::SysVendor, DmiField::ProductName, DmiField::ProductVersion])?;
+        let product = ProductId::from_streams(vendor, name, version)?;
+        let [vendor, chassis_type, version] = open_attributes(root, [DmiField::ChassisVendor, DmiField::ChassisType, DmiField::ChassisVersion])?;
+        let chassis = ChassisInfo::from_streams(vendor, chassis_type, version)?;
+        Ok(Self { board: BoardId::detect_at(root)?, product, chassis })
+    }
+}
+
+/// Summarizes the board, the product and the chassis, e.g.
+/// "LENOVO 20XWCTO1WW SDK0J40697 WIN, LENOVO 20XWCTO1WW ThinkPad X1 Carbon Gen 9, LENOVO chassis of type 10".
+impl Display for HardwareId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}, {}, {}", self.board, self.product, self.chassis)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    #[test]
+    fn detect_at() {
+        let root = TempDir::new("hardware");
+        for (attribute, value) in [
+            ("board_vendor", "LENOVO"), ("board_name", "20XWCTO1WW"), ("board_version", "SDK0J40697 WIN"),
+            ("sys_vendor", "LENOVO"), ("product_name", "20XWCTO1WW"), ("product_version", "ThinkPad X1 Carbon Gen 9"),
+            ("chassis_vendor", "LENOVO"), ("chassis_type", "10"), ("chassis_version", "None"),
+        ] {
+            root.write(format!("sys/class/dmi/id/{attribute}"), format!("{value}\n"));
+        }
+        let hardware = HardwareId::detect_at(root.path()).unwrap();
+        assert_eq!(hardware.board, BoardId::from_parts(Some(b"LENOVO"), Some(b"20XWCTO1WW"), Some(b"SDK0J40697 WIN")).unwrap());
+        assert_eq!(hardware.product.version(), Some(&b"ThinkPad X1 Carbon Gen 9"[..]));
+        assert_eq!(hardware.chassis, ChassisInfo { vendor: Some(b"LENOVO".to_vec()), chassis_type: Some(10), version: Some(b"None".to_vec()) });
+        assert_eq!(hardware.to_string(), "LENOVO 20XWCTO1WW SDK0J40697 WIN, LENOVO 20XWCTO1WW ThinkPad X1 Carbon Gen 9, LENOVO chassis of type 10");
+    }
+
+    #[test]
+    fn undetected() {
+        let hardware = HardwareId::detect_at(TempDir::new("hardware-empty").path()).unwrap();
+        assert_eq!(hardware.to_string(), "undetected motherboard, undetected product, chassis of undetected type");
+    }
+}
@@ -0,0 +1,81 @@
+//! [`BoardId::index_key`], a composite key for database indexing.
+
+use alloc::string::String;
+
+use crate::{group::normalize, BoardId, Part};
+
+/// The width of each part's field in an [index key](BoardId::index_key).
+const FIELD_WIDTH: usize = BoardId::BUFSZ;
+
+impl BoardId {
+    /// Gets a fixed-width, ASCII key of the board, whose lexical order is the order of its parts,
+    /// for indexing it with a single database column.
+    ///
+    /// The key is the vendor, name and version fields back to back, each 255 characters wide (765
+    /// bytes in all). A field is its [canonical](Self::part_canonical) part lowercased, with
+    /// whitespace runs collapsed into a space and trimmed (empty if undetected), with characters
+    /// outside printable ASCII replaced by `?`, and right-padded with spaces. The fields are padded
+    /// rather than length-prefixed, since a length prefix would sort by length first.
+    ///
+    /// Since a field never ends with a space, the padding sorts before any character the field could
+    /// continue with, so sorting the keys sorts by vendor, then name, then version, like sorting
+    /// `(vendor, name, version)` tuples of the fields does. That's also the order of the normalized
+    /// parts themselves as long as they're ASCII: non-ASCII characters all become `?`, so parts that
+    /// differ only in them get equal fields, and sort by the `?` rather than by the characters.
+    pub fn index_key(&self) -> String {
+        let mut key = String::with_capacity(FIELD_WIDTH * Part::ALL.len());
+        for part in Part::ALL {
+            let field = self.part_canonical(part).map(normalize).unwrap_or_default();
+            let start = key.len();
+            key.extend(field.chars().map(|c| if c == ' ' || c.is_ascii_graphic() { c } else { '?' }).take(FIELD_WIDTH));
+            key.extend(core::iter::repeat_n(' ', FIELD_WIDTH - (key.len() - start)));
+        }
+        key
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+    use crate::fixture::board;
+
+    #[test]
+    fn format() {
+        let key = board("ASUS", "PRIME  B550", "Rev 1.0").index_key();
+        assert_eq!(key.len(), 3 * 255);
+        assert_eq!(key[..255].trim_end(), "asus");
+        assert_eq!(key[255..510].trim_end(), "prime b550");
+        assert_eq!(key[510..].trim_end(), "rev 1.0");
+        assert_eq!(&board("Ä\u{1}", "", "").index_key()[..3], "?? ");
+        assert_eq!(BoardId::from_fixture("undetected").unwrap().index_key(), " ".repeat(765));
+    }
+
+    #[test]
+    fn sort_order() {
+        let boards = [
+            board("ASUS", "PRIME B550", "1.0"),
+            board("ASUS", "PRIME B550", ""),
+            board("", "NAME", ""),
+            board("ASUS", "PRIME", "9"),
+            board("ASUS", "PRIME B550-PLUS", ""),
+            board("Dell Inc.", "0XR032", "A00"),
+            board("asus", "PRIME B550", "0.9"),
+            board("ASUS", "PRIME!", ""),
+        ];
+        let logical = |board: &BoardId| Part::ALL.map(|part| board.part_canonical(part).map(normalize).unwrap_or_default());
+        let mut by_key = boards.to_vec();
+        by_key.sort_by_key(BoardId::index_key);
+        let mut by_parts = boards.to_vec();
+        by_parts.sort_by_key(logical);
+        assert_eq!(by_key.iter().map(logical).collect::<Vec<_>>(), by_parts.iter().map(logical).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn non_ascii() {
+        assert_eq!(board("Ä", "NAME", "").index_key(), board("Ö", "NAME", "").index_key());
+        assert!(board("ZOTAC", "", "").index_key() > board("Ä", "", "").index_key(), "non-ASCII sorts as `?`, before letters");
+        let ascii = board("A", "", "").index_key();
+        assert!(ascii > board("Ä", "", "").index_key());
+        assert!(ascii < board("AÄ", "", "").index_key());
+    }
+}
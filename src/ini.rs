@@ -0,0 +1,71 @@
+//! Rendering [`BoardId`]s as INI sections.
+
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::{BoardId, Part};
+
+/// Appends the bytes to the string, escaped for an INI value or section name: backslash escapes for
+/// the backslash, the comment and section delimiters, tab, CR and LF, and `\xHH` for other control
+/// characters and non-ASCII bytes.
+fn escape(out: &mut String, bytes: &[u8]) {
+    for &b in bytes {
+        match b {
+            b'\\' | b';' | b'#' | b'[' | b']' => { out.push('\\'); out.push(b as char) }
+            b'\t' => out.push_str("\\t"),
+            b'\r' => out.push_str("\\r"),
+            b'\n' => out.push_str("\\n"),
+            b if b.is_ascii_control() || !b.is_ascii() => { let _ = write!(out, "\\x{b:02X}"); }
+            b => out.push(b as char),
+        }
+    }
+}
+
+impl BoardId {
+    /// Renders the [`BoardId`] as an INI section with the given name, e.g.
+    /// `[board]\nvendor = ASUS\nname = PRIME\nversion = 1.0\n`.
+    ///
+    /// The keys are the [part names](Part::name), and undetected parts are omitted. The section
+    /// name and values are backslash-escaped (`\\`, `\;`, `\#`, `\[`, `\]`, `\t`, `\r`, `\n`, and
+    /// `\xHH` for other control characters and non-ASCII bytes), so values never span lines.
+    pub fn to_ini_section(&self, section: &str) -> String {
+        let mut ini = String::from("[");
+        escape(&mut ini, section.as_bytes());
+        ini.push_str("]\n");
+        for part in Part::ALL {
+            let Some(value) = self.part(part) else { continue };
+            ini.push_str(part.name());
+            ini.push_str(" = ");
+            escape(&mut ini, value);
+            ini.push('\n');
+        }
+        ini
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn full() {
+        let board = BoardId::from_parts(Some(b"ASUS"), Some(b"PRIME"), Some(b"1.0")).unwrap();
+        assert_eq!(board.to_ini_section("board"), "[board]\nvendor = ASUS\nname = PRIME\nversion = 1.0\n");
+    }
+
+    #[test]
+    fn partial() {
+        let board = BoardId::from_parts(None, Some(b"PRIME"), None).unwrap();
+        assert_eq!(board.to_ini_section("board"), "[board]\nname = PRIME\n");
+        assert_eq!(BoardId::from_fixture("undetected").unwrap().to_ini_section("board"), "[board]\n");
+    }
+
+    #[test]
+    fn escaped() {
+        let board = BoardId::from_parts(Some(b"A\nB"), Some(b"C; #1 \\ \xFF"), None).unwrap();
+        assert_eq!(board.to_ini_section("a]b"), r"[a\]b]
+vendor = A\nB
+name = C\; \#1 \\ \xFF
+");
+    }
+}
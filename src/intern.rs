@@ -0,0 +1,67 @@
+//! Interning [`BoardId`]s, for storing many that share parts.
+
+use std::{collections::HashSet, rc::Rc};
+
+use crate::BoardId;
+
+/// Deduplicates strings, handing out shared references to a single copy of each.
+#[derive(Debug, Default, Clone)]
+pub struct StringInterner {
+    /// The interned strings.
+    strings: HashSet<Rc<str>>,
+}
+
+impl StringInterner {
+    /// Creates an empty interner.
+    pub fn new() -> Self { Self::default() }
+
+    /// Gets the interned copy of the string, interning it if it's new.
+    pub fn intern(&mut self, s: &str) -> Rc<str> {
+        if let Some(interned) = self.strings.get(s) { return Rc::clone(interned) }
+        let interned = Rc::<str>::from(s);
+        self.strings.insert(Rc::clone(&interned));
+        interned
+    }
+
+    /// Gets the number of interned strings.
+    pub fn len(&self) -> usize { self.strings.len() }
+
+    /// Checks whether no string is interned.
+    pub fn is_empty(&self) -> bool { self.strings.is_empty() }
+}
+
+/// A [`BoardId`] whose parts are [interned](StringInterner).
+///
+/// The parts are (lossily) decoded as UTF-8.
+#[derive(Debug, Hash, Clone, PartialEq, PartialOrd, Eq, Ord)]
+pub struct InternedBoardId {
+    /// The board's vendor / brand.
+    pub vendor: Option<Rc<str>>,
+    /// The board's name / model.
+    pub name: Option<Rc<str>>,
+    /// The board's version.
+    pub version: Option<Rc<str>>,
+}
+
+impl BoardId {
+    /// Gets the [`BoardId`] with its parts interned, so boards sharing parts share their memory.
+    pub fn intern(&self, interner: &mut StringInterner) -> InternedBoardId {
+        let mut intern = |part: Option<&[u8]>| part.map(|part| interner.intern(&String::from_utf8_lossy(part)));
+        InternedBoardId { vendor: intern(self.vendor()), name: intern(self.name()), version: intern(self.version()) }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn shared() {
+        let mut interner = StringInterner::new();
+        let a = BoardId::from_parts(Some(b"VENDOR"), Some(b"NAME-A"), None).unwrap().intern(&mut interner);
+        let b = BoardId::from_parts(Some(b"VENDOR"), Some(b"NAME-B"), None).unwrap().intern(&mut interner);
+        assert!(Rc::ptr_eq(a.vendor.as_ref().unwrap(), b.vendor.as_ref().unwrap()));
+        assert_eq!((a.name.as_deref(), b.name.as_deref(), a.version), (Some("NAME-A"), Some("NAME-B"), None));
+        assert_eq!(interner.len(), 3);
+    }
+}
@@ -0,0 +1,283 @@
+//! The JSON form of [`BoardId`]s, and verifying detection against saved baselines.
+
+use std::{fmt::Write, fs, io, path::Path, str::CharIndices};
+
+use crate::{BoardId, Part};
+
+/// The result of [verifying](BoardId::verify_against) detection against a baseline.
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub enum VerifyResult {
+    /// Every part matches the baseline.
+    Matched,
+    /// The parts that differ from the baseline (the rest match).
+    Changed(Vec<Part>),
+    /// There's no baseline.
+    NoBaseline,
+}
+
+impl BoardId {
+    /// Renders the [`BoardId`] as a JSON object of its parts, e.g.
+    /// `{"vendor":"ASUS","name":"PRIME","version":null}`, with null for undetected parts.
+    ///
+    /// Parts that aren't valid UTF-8 are converted lossily. This is the format of the CLI's
+    /// `--json` output, and of baselines (see [`Self::verify_against`]).
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{");
+        for (i, part) in Part::ALL.into_iter().enumerate() {
+            if i > 0 { json.push(',') }
+            let _ = write!(json, "\"{}\":", part.name());
+            let Some(value) = self.part(part) else { json.push_str("null"); continue };
+            push_string(&mut json, &String::from_utf8_lossy(value));
+        }
+        json.push('}');
+        json
+    }
+
+    /// Parses a [`BoardId`] from its [JSON form](Self::to_json).
+    ///
+    /// Members other than the parts are ignored, and missing parts are undetected. Fails with
+    /// [`io::ErrorKind::InvalidData`] if the JSON isn't an object whose parts are strings or null.
+    pub fn from_json(json: &str) -> io::Result<Self> {
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, "invalid board ID JSON");
+        let mut parser = Parser(json.trim_start());
+        let mut parts: [Option<String>; 3] = Default::default();
+        parser.expect('{').ok_or_else(invalid)?;
+        if parser.expect('}').is_none() {
+            loop {
+                let key = parser.string().ok_or_else(invalid)?;
+                parser.expect(':').ok_or_else(invalid)?;
+                let value = if parser.keyword("null") { None } else { Some(parser.string().ok_or_else(invalid)?) };
+                if let Some(part) = Part::ALL.into_iter().find(|part| part.name() == key) { parts[part as usize] = value }
+                if parser.expect('}').is_some() { break }
+                parser.expect(',').ok_or_else(invalid)?;
+            }
+        }
+        if !parser.0.trim().is_empty() { return Err(invalid()) }
+        let [vendor, name, version] = parts;
+        Ok(Self::from_opt_strings(vendor, name, version)?)
+    }
+
+    /// Detects the [`BoardId`] and saves its [JSON form](Self::to_json) to the given path, e.g. as
+    /// a baseline for [`Self::verify_against`].
+    pub fn detect_to_json_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let board = Self::detect()?;
+        fs::write(path, board.to_json())?;
+        Ok(board)
+    }
+
+    /// Detects the [`BoardId`] and compares it against the baseline [JSON](Self::to_json) at the
+    /// given path, e.g. to detect tampering or drift.
+    ///
+    /// A missing baseline is [`VerifyResult::NoBaseline`], rather than an error.
+    pub fn verify_against(path: impl AsRef<Path>) -> io::Result<VerifyResult> { Self::detect()?.compare_to_baseline(path) }
+
+    /// Compares the [`BoardId`] against the baseline [JSON](Self::to_json) at the given path.
+    ///
+    /// The parts are compared as converted to UTF-8 lossily, as the baseline's were, so a board
+    /// with non-UTF-8 parts matches its own baseline. See [`Self::verify_against`].
+    pub fn compare_to_baseline(&self, path: impl AsRef<Path>) -> io::Result<VerifyResult> {
+        let baseline = match fs::read_to_string(path) {
+            Ok(json) => Self::from_json(&json)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(VerifyResult::NoBaseline),
+            Err(e) => return Err(e),
+        };
+        let lossy = |part: Option<&[u8]>| part.map(|part| String::from_utf8_lossy(part).into_owned());
+        let changed = Part::ALL.into_iter().filter(|&part| lossy(self.part(part)) != lossy(baseline.part(part))).collect::<Vec<_>>();
+        Ok(if changed.is_empty() { VerifyResult::Matched } else { VerifyResult::Changed(changed) })
+    }
+}
+
+/// Pushes the string onto the JSON as a string literal, with quotes, backslashes and control
+/// characters escaped.
+pub(crate) fn push_string(json: &mut String, s: &str) {
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' | '\\' => { json.push('\\'); json.push(c) }
+            c if c.is_control() => { let _ = write!(json, "\\u{:04x}", c as u32); }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+/// A parsed JSON value, for documents (e.g. `lshw -json` output) that nest.
+#[cfg(feature = "json")]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    /// `null`, `true`, `false` or a number, whose value isn't needed.
+    Scalar,
+    /// A string.
+    String(String),
+    /// An array.
+    Array(Vec<Value>),
+    /// An object's members, in order.
+    Object(Vec<(String, Value)>),
+}
+
+#[cfg(feature = "json")]
+impl Value {
+    /// The deepest nesting parsed, so hostile input can't overflow the stack.
+    const MAX_DEPTH: usize = 128;
+
+    /// Parses a whole JSON document, returning `None` if it's invalid or nested too deeply.
+    pub(crate) fn parse(json: &str) -> Option<Self> {
+        let mut parser = Parser(json);
+        let value = parser.value(Self::MAX_DEPTH)?;
+        parser.0.trim().is_empty().then_some(value)
+    }
+
+    /// Gets the member of the object by its key, if it's an object that has it.
+    pub(crate) fn get(&self, key: &str) -> Option<&Self> {
+        let Self::Object(members) = self else { return None };
+        members.iter().find(|(k, _)| k == key).map(|(_, value)| value)
+    }
+
+    /// Gets the string, if it's a string.
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self { Self::String(string) => Some(string), _ => None }
+    }
+}
+
+/// A minimal JSON parser over the rest of the input.
+struct Parser<'a>(&'a str);
+
+impl Parser<'_> {
+    /// Consumes a value (after whitespace), nested at most `depth` deep.
+    #[cfg(feature = "json")]
+    fn value(&mut self, depth: usize) -> Option<Value> {
+        let depth = depth.checked_sub(1)?;
+        match self.0.trim_start().chars().next()? {
+            '"' => self.string().map(Value::String),
+            '[' => {
+                self.expect('[')?;
+                let mut values = Vec::new();
+                if self.expect(']').is_some() { return Some(Value::Array(values)) }
+                loop {
+                    values.push(self.value(depth)?);
+                    if self.expect(']').is_some() { return Some(Value::Array(values)) }
+                    self.expect(',')?;
+                }
+            }
+            '{' => {
+                self.expect('{')?;
+                let mut members = Vec::new();
+                if self.expect('}').is_some() { return Some(Value::Object(members)) }
+                loop {
+                    let key = self.string()?;
+                    self.expect(':')?;
+                    members.push((key, self.value(depth)?));
+                    if self.expect('}').is_some() { return Some(Value::Object(members)) }
+                    self.expect(',')?;
+                }
+            }
+            _ if ["null", "true", "false"].into_iter().any(|keyword| self.keyword(keyword)) => Some(Value::Scalar),
+            _ => {
+                let rest = self.0.trim_start();
+                let len = rest.find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c))).unwrap_or(rest.len());
+                rest[..len].parse::<f64>().ok()?;
+                self.0 = &rest[len..];
+                Some(Value::Scalar)
+            }
+        }
+    }
+
+    /// Consumes the character (after whitespace), if it's next.
+    fn expect(&mut self, c: char) -> Option<()> {
+        self.0 = self.0.trim_start().strip_prefix(c)?;
+        Some(())
+    }
+
+    /// Consumes the keyword (after whitespace), returning whether it was next.
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let Some(rest) = self.0.trim_start().strip_prefix(keyword) else { return false };
+        self.0 = rest;
+        true
+    }
+
+    /// Consumes a string (after whitespace), decoding its escapes.
+    fn string(&mut self) -> Option<String> {
+        self.expect('"')?;
+        let mut string = String::new();
+        let mut chars = self.0.char_indices();
+        loop {
+            let (i, c) = chars.next()?;
+            match c {
+                '"' => { self.0 = &self.0[i + 1..]; return Some(string) }
+                '\\' => {
+                    let escaped = match chars.next()?.1 {
+                        c @ ('"' | '\\' | '/') => c,
+                        'b' => '\u{8}', 'f' => '\u{c}', 'n' => '\n', 'r' => '\r', 't' => '\t',
+                        'u' => {
+                            let high = hex_unit(&mut chars)?;
+                            match high {
+                                0xD800..=0xDBFF => {
+                                    if (chars.next()?.1, chars.next()?.1) != ('\\', 'u') { return None }
+                                    char::decode_utf16([high, hex_unit(&mut chars)?]).next()?.ok()?
+                                }
+                                _ => char::from_u32(high as u32)?,
+                            }
+                        }
+                        _ => return None,
+                    };
+                    string.push(escaped);
+                }
+                c if c.is_control() => return None,
+                c => string.push(c),
+            }
+        }
+    }
+}
+
+/// Consumes the four hexadecimal digits of a `\u` escape.
+fn hex_unit(chars: &mut CharIndices) -> Option<u16> {
+    let digits = chars.by_ref().take(4).map(|(_, c)| c).collect::<String>();
+    if digits.len() != 4 || !digits.chars().all(|c| c.is_ascii_hexdigit()) { return None }
+    u16::from_str_radix(&digits, 16).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    fn board() -> BoardId { BoardId::from_parts(Some(b"VENDOR"), Some(b"NA\"ME\x01"), None).unwrap() }
+
+    #[test]
+    fn round_trip() {
+        assert_eq!(board().to_json(), r#"{"vendor":"VENDOR","name":"NA\"ME\u0001","version":null}"#);
+        assert_eq!(BoardId::from_json(&board().to_json()).unwrap(), board());
+        let json = " { \"version\" : \"\\u00e9\\ud83d\\ude00\", \"other\": \"x\" } \n";
+        assert_eq!(BoardId::from_json(json).unwrap().version(), Some("é😀".as_bytes()));
+        assert_eq!(BoardId::from_json("{}").unwrap(), BoardId::from_parts(None, None, None).unwrap());
+    }
+
+    #[test]
+    fn invalid() {
+        for json in ["", "{", "[]", r#"{"vendor":1}"#, r#"{"vendor":"A",}"#, r#"{"vendor":"A"} x"#, r#"{"vendor":"\q"}"#] {
+            assert_eq!(BoardId::from_json(json).unwrap_err().kind(), io::ErrorKind::InvalidData, "{json}");
+        }
+    }
+
+    #[test]
+    fn baseline() {
+        let dir = TempDir::new("json-baseline");
+        let path = dir.write("baseline.json", board().to_json());
+        assert_eq!(board().compare_to_baseline(&path).unwrap(), VerifyResult::Matched);
+        let drifted = BoardId::from_parts(Some(b"VENDOR"), Some(b"OTHER"), Some(b"1.0")).unwrap();
+        assert_eq!(drifted.compare_to_baseline(&path).unwrap(), VerifyResult::Changed(vec![Part::Name, Part::Version]));
+        assert_eq!(board().compare_to_baseline(dir.path().join("missing.json")).unwrap(), VerifyResult::NoBaseline);
+    }
+
+    #[test]
+    fn not_utf8_baseline() {
+        let board = BoardId::from_parts(Some(b"VENDOR\xff"), Some(b"NAME"), None).unwrap();
+        let parsed = BoardId::from_json(&board.to_json()).unwrap();
+        assert_eq!(parsed.vendor(), Some("VENDOR\u{fffd}".as_bytes()));
+        let dir = TempDir::new("json-baseline-not-utf8");
+        let path = dir.write("baseline.json", board.to_json());
+        assert_eq!(board.compare_to_baseline(&path).unwrap(), VerifyResult::Matched);
+        let drifted = BoardId::from_parts(Some(b"VENDOR\xff"), Some(b"OTHER"), None).unwrap();
+        assert_eq!(drifted.compare_to_baseline(&path).unwrap(), VerifyResult::Changed(vec![Part::Name]));
+    }
+}
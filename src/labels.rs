@@ -0,0 +1,75 @@
+//! [`BoardId::labels`], for exporting the [`BoardId`] as metrics labels.
+
+use alloc::string::String;
+
+use crate::{BoardId, DmiField, Part};
+
+/// The maximum length of a [label](BoardId::labels) value.
+pub const MAX_LABEL_LEN: usize = 64;
+
+/// Sanitizes the value as per [`BoardId::labels`].
+fn sanitize(value: &[u8]) -> String {
+    let mut label = String::with_capacity(value.len().min(MAX_LABEL_LEN));
+    for &b in value {
+        let safe = b.is_ascii_alphanumeric() || b"-._".contains(&b);
+        if safe { label.push(b as char) } else if !label.ends_with('_') { label.push('_') }
+    }
+    // the label is ASCII, so it can be truncated anywhere; truncating first makes sure the cut
+    // doesn't leave a trailing `_`
+    label.truncate(MAX_LABEL_LEN);
+    String::from(label.trim_matches('_'))
+}
+
+impl BoardId {
+    /// Gets the parts as metrics (e.g. Prometheus) labels: `board_vendor`, `board_name` and
+    /// `board_version`, in that order.
+    ///
+    /// The values are sanitized to ASCII alphanumerics, `-`, `.` and `_`: every run of other bytes
+    /// (whitespace, quotes, control characters, each byte of non-ASCII UTF-8, etc.) is replaced by
+    /// a single `_`, then the value is truncated to [`MAX_LABEL_LEN`] bytes, and leading and
+    /// trailing `_`s are trimmed. So "ASUSTeK COMPUTER INC." becomes "ASUSTeK_COMPUTER_INC.".
+    /// Undetected parts, and parts that sanitize to nothing, are omitted.
+    pub fn labels(&self) -> impl Iterator<Item = (&'static str, String)> + '_ {
+        [(Part::Vendor, DmiField::BoardVendor), (Part::Name, DmiField::BoardName), (Part::Version, DmiField::BoardVersion)]
+            .into_iter()
+            .filter_map(|(part, field)| Some((field.attribute(), sanitize(self.part(part)?))))
+            .filter(|(_, value)| !value.is_empty())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    fn labels(vendor: Option<&[u8]>, name: Option<&[u8]>, version: Option<&[u8]>) -> Vec<(&'static str, String)> {
+        BoardId::from_parts(vendor, name, version).unwrap().labels().collect()
+    }
+
+    #[test]
+    fn sanitized() {
+        assert_eq!(labels(Some(b"ASUSTeK COMPUTER INC."), Some(b"PRIME B550-PLUS"), Some(b"Rev X.0x")), [
+            ("board_vendor" , "ASUSTeK_COMPUTER_INC.".to_owned()),
+            ("board_name"   , "PRIME_B550-PLUS".to_owned()),
+            ("board_version", "Rev_X.0x".to_owned()),
+        ]);
+        assert_eq!(sanitize(b"\"quoted\" \\ name"), "quoted_name");
+        assert_eq!(sanitize(b"line\nbreak\r\n"), "line_break");
+        assert_eq!(sanitize("Carte mère Ünïcode".as_bytes()), "Carte_m_re_n_code");
+        assert_eq!(sanitize(b"\xff\xfe"), "");
+    }
+
+    #[test]
+    fn omitted() {
+        assert_eq!(labels(None, Some(b"NAME"), Some("\"€\"".as_bytes())), [("board_name", "NAME".to_owned())]);
+        assert_eq!(labels(None, None, None), []);
+    }
+
+    #[test]
+    fn capped() {
+        let [(_, value)] = labels(None, Some(&[b'N'; 200]), None).try_into().unwrap();
+        assert_eq!(value.len(), MAX_LABEL_LEN);
+        assert_eq!(sanitize(&[b'_'; 200]), "");
+        let cut = [&[b'N'; MAX_LABEL_LEN - 1][..], b" ", &[b'N'; 10]].concat();
+        assert_eq!(sanitize(&cut), "N".repeat(MAX_LABEL_LEN - 1), "the cut left a trailing `_`");
+    }
+}
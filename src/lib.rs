@@ -1,9 +1,119 @@
 //! [`BoardId`] detection.
-
+//!
+//! Detection requires the default `std` feature. Without it the crate is `no_std` (with `alloc`),
+//! keeping the core [`BoardId`] type: [`BoardId::from_parts`], the accessors, and [`Display`].
+//!
+//! Only Linux has a detection backend. Elsewhere detection finds nothing, which [`supported`] tells
+//! ahead of time.
+
+#![cfg_attr(not(feature = "std"), no_std)]
 #![forbid(missing_docs, unsafe_code)]
-#[cfg(not(target_os = "linux"))] compile_error!("Only Linux is supported for the time being.");
 
-use std::{io::{self, Read}, fs::File, fmt::Display, path::Path};
+extern crate alloc;
+
+use alloc::{string::{String, ToString}, vec::Vec};
+use core::{fmt::{self, Display}, hash::Hasher};
+#[cfg(feature = "std")] use std::{io::{self, Read}, fs::File, path::Path};
+
+#[cfg(feature = "std")] mod best_id;
+#[cfg(feature = "std")] mod bios;
+mod borrowed;
+mod buf;
+#[cfg(feature = "build-override")] mod build_override;
+#[cfg(feature = "std")] mod cache;
+mod chipset;
+#[cfg(feature = "chipset-db")] mod chipset_db;
+#[cfg(feature = "std")] mod context;
+#[cfg(feature = "dmidecode")] mod cross_check;
+#[cfg(feature = "std")] mod cstring;
+#[cfg(feature = "std")] mod devicetree;
+#[cfg(feature = "std")] mod diagnose;
+mod dmi;
+mod dmidecode;
+mod fingerprint;
+#[cfg(feature = "std")] mod firmware;
+#[cfg(feature = "std")] mod form_factor;
+mod glob;
+#[cfg(feature = "std")] mod global;
+mod group;
+#[cfg(feature = "std")] mod hardware;
+mod index_key;
+mod ini;
+#[cfg(feature = "std")] mod intern;
+#[cfg(feature = "std")] mod json;
+mod labels;
+#[cfg(feature = "json")] mod lshw;
+#[cfg(feature = "std")] mod machine_id;
+#[cfg(feature = "std")] mod map;
+mod matcher;
+#[cfg(feature = "std")] mod mismatch;
+mod modalias;
+#[cfg(feature = "std")] mod options;
+#[cfg(feature = "std")] mod pin;
+mod placeholder;
+#[cfg(feature = "std")] mod probe;
+mod query;
+mod quirks;
+mod redact;
+mod sample;
+mod sanitize;
+mod sbc;
+#[cfg(feature = "std")] mod scan;
+mod search;
+#[cfg(feature = "std")] mod server;
+mod similarity;
+#[cfg(feature = "std")] mod smbios;
+#[cfg(feature = "std")] mod snapshot;
+#[cfg(feature = "std")] mod sosreport;
+#[cfg(feature = "std")] mod support;
+#[cfg(feature = "std")] mod sysfs;
+#[cfg(feature = "std")] mod sysfs_write;
+#[cfg(feature = "std")] mod utf16;
+mod vendor;
+#[cfg(feature = "std")] mod watch;
+mod whitespace;
+mod wmi;
+#[cfg(all(test, feature = "std"))] mod fixture;
+
+#[cfg(feature = "std")] pub use best_id::{best_id, best_id_at, BestId};
+#[cfg(feature = "std")] pub use cache::Origin;
+#[cfg(feature = "std")] pub use context::DetectContext;
+#[cfg(feature = "dmidecode")] pub use cross_check::{CommandRunner, CrossCheck, SystemRunner};
+#[cfg(feature = "std")] pub use cstring::BoardIdC;
+#[cfg(feature = "std")] pub use diagnose::{diagnose, diagnose_at, DiagnosticReport, DiagnosticValue, FileDiagnostic};
+#[cfg(feature = "std")] pub use dmi::{best_model, best_model_at, detect_inventory, detect_inventory_at};
+#[cfg(feature = "std")] pub use firmware::{firmware_flags, firmware_flags_at, FirmwareFlags, SecureBoot};
+#[cfg(feature = "std")] pub use form_factor::{Confidence, FormFactor};
+#[cfg(feature = "std")] pub use global::{get, get_or_init_with};
+#[cfg(feature = "std")] pub use hardware::{ChassisInfo, HardwareId, ProductId};
+#[cfg(feature = "std")] pub use intern::{InternedBoardId, StringInterner};
+#[cfg(feature = "std")] pub use json::VerifyResult;
+#[cfg(feature = "json")] pub use lshw::LshwError;
+#[cfg(feature = "std")] pub use machine_id::{machine_id, machine_id_at};
+#[cfg(feature = "std")] pub use mismatch::Mismatch;
+#[cfg(feature = "std")] pub use options::{DetectOptions, MissingRequired, Provenance};
+#[cfg(feature = "std")] pub use pin::{PartChange, PinMismatch, PinnedBoardId};
+#[cfg(feature = "std")] pub use probe::{FsOpener, NoFollowOpener, Opener, Prober};
+#[cfg(feature = "std")] pub use scan::{detect_many, probe_paths, probe_paths_at, ScanSession, Source};
+#[cfg(feature = "std")] pub use smbios::{smbios_version, smbios_version_at};
+#[cfg(feature = "std")] pub use snapshot::SnapshotMismatch;
+#[cfg(feature = "std")] pub use support::{supported, supported_at, Support};
+#[cfg(feature = "std")] pub use watch::BoardIdChange;
+pub use borrowed::{BoardIdRef, BufferTooSmall};
+pub use buf::BoardIdBuf;
+pub use chipset::CHIPSETS;
+#[cfg(feature = "chipset-db")] pub use chipset_db::{Chipset, ChipsetVendor};
+pub use dmi::{requires_root, DmiField};
+pub use fingerprint::CANONICAL_SEPARATOR;
+pub use glob::{glob_match, Rule};
+pub use group::GroupKeyOptions;
+pub use labels::MAX_LABEL_LEN;
+pub use matcher::{BoardMatcher, MatcherEntry};
+pub use modalias::{parse_modalias, ModaliasFields};
+pub use placeholder::{is_placeholder, looks_like_serial};
+pub use redact::{RedactMask, Redacted};
+pub use sbc::SbcInfo;
+pub use vendor::CORPORATE_SUFFIXES;
 
 /// Motherboard ID.
 #[derive(Debug, Hash, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
@@ -23,55 +133,183 @@ impl BoardId {
     const BUFSZ: usize = u8::MAX as usize;
 
     /// Attempts to detect the [`BoardId`].
-    pub fn detect() -> io::Result<Self> {
-        /// Opens a file, returning `Ok(None)` if it doesn't exist.
-        fn open_existing_file(path: impl AsRef<Path>) -> io::Result<Option<File>> {
-            match File::open(path) {
-                Ok(file) => Ok(Some(file)),
-                Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
-                Err(e) => Err(e),
-            }
+    ///
+    /// The board is read from DMI, falling back to the device tree (for e.g. ARM boards that have
+    /// no DMI) when DMI detects nothing.
+    /// Sysfs is expected at `/sys`; only [`Self::detect_with_options`] discovers it elsewhere (see
+    /// [`DetectOptions::discover_sysfs`]).
+    ///
+    /// With the `build-override` feature, and the `BOARD_ID_OVERRIDE` environment variable set to
+    /// `VENDOR|NAME|VERSION` at compile time, that ID is returned instead, without any I/O.
+    #[cfg(feature = "std")]
+    pub fn detect() -> io::Result<Self> { Ok(Self::detect_with_provenance()?.0) }
+
+    /// Attempts to detect the [`BoardId`] of the system whose filesystem is rooted at the given
+    /// path, e.g. a mounted image.
+    ///
+    /// See [`Self::detect`].
+    #[cfg(feature = "std")]
+    pub fn detect_at(root: impl AsRef<Path>) -> io::Result<Self> { ScanSession::new().detect_at(root) }
+
+    /// Attempts to detect the [`BoardId`] from DMI, along with the raw (read) and stripped lengths
+    /// of each part, in vendor, name, version order.
+    ///
+    /// This is for diagnosing oddly terminated attributes (e.g. double or missing newlines), so
+    /// unlike [`Self::detect`] there's no device tree fallback. See
+    /// [`Self::detect_with_lengths_at`].
+    #[cfg(feature = "std")]
+    pub fn detect_with_lengths() -> io::Result<(Self, [(usize, usize); 3])> { Self::detect_with_lengths_at("/") }
+
+    /// Attempts to detect the [`BoardId`] of the system whose filesystem is rooted at the given
+    /// path from DMI, along with the raw (read) and stripped lengths of each part.
+    ///
+    /// See [`Self::detect_with_lengths`].
+    #[cfg(feature = "std")]
+    pub fn detect_with_lengths_at(root: impl AsRef<Path>) -> io::Result<(Self, [(usize, usize); 3])> {
+        let dir = root.as_ref().join(dmi::DIR);
+        Self::from_streams_with_lengths(
+            NewlinePolicy::default(),
+            open_existing_file(dir.join(DmiField::BoardVendor .attribute()))?,
+            open_existing_file(dir.join(DmiField::BoardName   .attribute()))?,
+            open_existing_file(dir.join(DmiField::BoardVersion.attribute()))?,
+        )
+    }
+
+    /// Attempts to detect the [`BoardId`], along with the [`Source`] it was detected from (`None`
+    /// if undetected).
+    ///
+    /// All the parts come from the same source. See [`Self::detect`].
+    #[cfg(feature = "std")]
+    pub fn detect_with_provenance() -> io::Result<(Self, Option<Source>)> {
+        #[cfg(feature = "build-override")]
+        if let Some(board) = build_override::board()? { return Ok((board, Some(Source::BuildOverride))) }
+        Self::detect_with_provenance_at("/")
+    }
+
+    /// Attempts to detect the [`BoardId`] of the system whose filesystem is rooted at the given
+    /// path, along with the [`Source`] it was detected from (`None` if undetected).
+    ///
+    /// See [`Self::detect_at`].
+    #[cfg(feature = "std")]
+    pub fn detect_with_provenance_at(root: impl AsRef<Path>) -> io::Result<(Self, Option<Source>)> {
+        ScanSession::new().detect_with_provenance_at(root)
+    }
+
+    /// Attempts to make a [`BoardId`] from its parts.
+    ///
+    /// Empty parts are treated as undetected.
+    /// Fails if the parts don't fit in the buffer, naming the first part that doesn't.
+    pub fn from_parts(vendor: Option<&[u8]>, name: Option<&[u8]>, version: Option<&[u8]>) -> Result<Self, CapacityError> {
+        let parts = [vendor, name, version].map(Option::unwrap_or_default);
+        let mut end = 0;
+        for (part, value) in Part::ALL.into_iter().zip(parts) {
+            let remaining = Self::BUFSZ - end;
+            if value.len() > remaining { return Err(CapacityError { part, attempted: value.len(), remaining }) }
+            end += value.len();
         }
-        macro_rules! dmi { ($part:expr) => { open_existing_file(concat!("/sys/class/dmi/id/board_", $part)) }; }
-        Self::from_streams(dmi!("vendor")?, dmi!("name")?, dmi!("version")?)
+        let [vendor, name, version] = parts;
+        let (vendor_end, name_end) = (vendor.len(), vendor.len() + name.len());
+        let version_end = name_end + version.len();
+        let mut buffer = [0u8; Self::BUFSZ];
+        buffer[..vendor_end].copy_from_slice(vendor);
+        buffer[vendor_end..name_end].copy_from_slice(name);
+        buffer[name_end..version_end].copy_from_slice(version);
+        Ok(Self {
+            buffer,
+             vendor: vendor_end  as u8,
+               name: name_end    as u8,
+            version: version_end as u8,
+        })
     }
 
-    /// Attempts to detect the [`BoardId`] from stream IDs.
+    /// Attempts to make a [`BoardId`] from owned string parts, e.g. read from a configuration.
+    ///
+    /// See [`Self::from_parts`].
+    pub fn from_opt_strings(vendor: Option<String>, name: Option<String>, version: Option<String>) -> Result<Self, CapacityError> {
+        Self::from_parts(vendor.as_deref().map(str::as_bytes), name.as_deref().map(str::as_bytes), version.as_deref().map(str::as_bytes))
+    }
+
+    /// Attempts to make a [`BoardId`] from readers of its parts, e.g. of `/sys/class/dmi/id/board_*`
+    /// files proxied from another machine.
+    ///
+    /// Each reader is read to its end, and is expected to contain just its part followed by a
+    /// line terminator, as sysfs writes them. A single trailing LF or CRLF is stripped (the default
+    /// [`NewlinePolicy`]; see [`Self::from_streams_with`] for others), and nothing else is trimmed.
+    /// A `None` reader, or a part that's empty once stripped, is undetected.
+    ///
+    /// The parts share the 255-byte buffer, and each is read with a byte to spare (to see the end of
+    /// its reader), so each part's raw length plus the stripped lengths of the parts before it must
+    /// stay below 255. Otherwise it fails with an [`io::ErrorKind::WriteZero`] error (from a
+    /// [`CapacityError`]), as it does with the readers' own errors.
+    ///
+    /// ```
+    /// # use board_id::BoardId;
+    /// let board = BoardId::from_streams(Some("ASUS\n".as_bytes()), Some("PRIME B550\r\n".as_bytes()), None::<&[u8]>).unwrap();
+    /// assert_eq!(board.vendor(), Some(&b"ASUS"[..]));
+    /// assert_eq!(board.name(), Some(&b"PRIME B550"[..]));
+    /// assert_eq!(board.version(), None);
+    ///
+    /// // only one terminator is stripped, and empty parts are undetected
+    /// let board = BoardId::from_streams(Some(" ASUS\n\n".as_bytes()), Some("\n".as_bytes()), None::<&[u8]>).unwrap();
+    /// assert_eq!(board.vendor(), Some(&b" ASUS\n"[..]));
+    /// assert_eq!(board.name(), None);
+    ///
+    /// // too large
+    /// let long = "N".repeat(255);
+    /// let e = BoardId::from_streams(None::<&[u8]>, Some(long.as_bytes()), None::<&[u8]>).unwrap_err();
+    /// assert_eq!(e.kind(), std::io::ErrorKind::WriteZero);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_streams(vendor: Option<impl Read>, name: Option<impl Read>, version: Option<impl Read>) -> io::Result<Self> {
+        Self::from_streams_with(NewlinePolicy::default(), vendor, name, version)
+    }
+
+    /// Attempts to detect the [`BoardId`] from stream IDs, trimming each part per the given
+    /// [`NewlinePolicy`].
     ///
     /// The streams are expected to have the format of the `/sys/class/dmi/id/board_*` files,
-    /// i.e. contain just their respective part with a trailing NL.
+    /// i.e. contain just their respective part, with whatever terminator the policy expects.
     ///
     /// There's no intermediary buffer for efficiency, so this makes the buffer size effectively
     /// be `Self::BUFSZ - 2`, which hardly matters.
     /// The buffer size is reduced by two because when the input exactly fits, the last byte is
     /// going to be NL. And we need a spare byte so we'll have room to read and see if we reached
     /// EOF.
-    fn from_streams(vendor: Option<impl Read>, name: Option<impl Read>, version: Option<impl Read>) -> io::Result<Self> {
+    #[cfg(feature = "std")]
+    pub fn from_streams_with(policy: NewlinePolicy, vendor: Option<impl Read>, name: Option<impl Read>, version: Option<impl Read>) -> io::Result<Self> {
+        Ok(Self::from_streams_with_lengths(policy, vendor, name, version)?.0)
+    }
+
+    /// Same as [`Self::from_streams_with`], also returning the raw (read) and stripped lengths of
+    /// each part.
+    #[cfg(feature = "std")]
+    fn from_streams_with_lengths(policy: NewlinePolicy, vendor: Option<impl Read>, name: Option<impl Read>, version: Option<impl Read>) -> io::Result<(Self, [(usize, usize); 3])> {
         let mut buffer = [0u8; Self::BUFSZ];
 
-        fn read(buffer: &mut [u8], mut stream: impl Read) -> io::Result<usize> {
+        fn read(buffer: &mut [u8], part: Part, mut stream: impl Read, policy: NewlinePolicy) -> io::Result<(usize, usize)> {
             let mut n = 0;
             loop {
                 let buf = &mut buffer[n..];
-                if buf.is_empty() {
-                    return Err(io::Error::new(io::ErrorKind::WriteZero, "the motherboard ID is abnormally large and doesn't fit in the buffer"))
-                }
+                // the last byte is spare, to see the end of the stream
+                if buf.is_empty() { return Err(CapacityError { part, attempted: n, remaining: n.saturating_sub(1) }.into()) }
                 let m = stream.read(buf)?;
                 if m == 0 { break } else { n += m }
             }
-            Ok(n.saturating_sub(1)) // remove trailing NL
+            Ok((n, policy.trimmed_len(&buffer[..n])))
         }
 
-        let vendor_count  = vendor .map_or(Ok(0), |r| read(&mut buffer,                              r))?;
-        let name_count    = name   .map_or(Ok(0), |r| read(&mut buffer[vendor_count..],              r))?;
-        let version_count = version.map_or(Ok(0), |r| read(&mut buffer[vendor_count + name_count..], r))?;
+        let vendor  = vendor .map_or(Ok((0, 0)), |r| read(&mut buffer,                      Part::Vendor , r, policy))?;
+        let name    = name   .map_or(Ok((0, 0)), |r| read(&mut buffer[vendor.1..],          Part::Name   , r, policy))?;
+        let version = version.map_or(Ok((0, 0)), |r| read(&mut buffer[vendor.1 + name.1..], Part::Version, r, policy))?;
+        let (vendor_count, name_count, version_count) = (vendor.1, name.1, version.1);
+        buffer[vendor_count + name_count + version_count..].fill(0); // clear stripped terminators so equal IDs compare equal
 
-        Ok(Self {
+        Ok((Self {
             buffer,
              vendor:  vendor_count                               as u8,
                name: (vendor_count + name_count                ) as u8,
             version: (vendor_count + name_count + version_count) as u8,
-        })
+        }, [vendor, name, version]))
     }
 
     /// Gets the board's vendor / brand.
@@ -91,6 +329,254 @@ impl BoardId {
     pub fn version(&self) -> Option<&[u8]> {
         (self.name < self.version).then_some(&self.buffer[self.name as usize..self.version as usize])
     }
+
+    /// Gets the given part.
+    #[inline]
+    pub fn part(&self, part: Part) -> Option<&[u8]> {
+        match part {
+            Part::Vendor  => self.vendor(),
+            Part::Name    => self.name(),
+            Part::Version => self.version(),
+        }
+    }
+
+    /// Gets the vendor, name and version together, e.g. for matching on them at once.
+    #[inline]
+    #[allow(clippy::type_complexity)] // spelled out, so callers see what they match on
+    pub fn as_tuple(&self) -> (Option<&[u8]>, Option<&[u8]>, Option<&[u8]>) {
+        (self.vendor(), self.name(), self.version())
+    }
+
+    /// Merges two partial detections, taking each part from `self` if detected, otherwise from
+    /// `other`.
+    ///
+    /// Fails if the merged parts don't fit in the buffer.
+    pub fn merge(&self, other: &BoardId) -> Result<BoardId, CapacityError> {
+        let [vendor, name, version] = Part::ALL.map(|part| self.part(part).or(other.part(part)));
+        Self::from_parts(vendor, name, version)
+    }
+
+    /// Feeds the detected parts into the given [`Hasher`].
+    ///
+    /// Unlike the derived [`Hash`], which hashes the raw buffer, this only hashes the parts, each
+    /// prefixed by its length so that e.g. "AB" + "C" and "A" + "BC" don't collide.
+    pub fn hash_logical<H: Hasher>(&self, h: &mut H) {
+        for part in [self.vendor(), self.name(), self.version()] {
+            let part = part.unwrap_or_default();
+            h.write_usize(part.len());
+            h.write(part);
+        }
+    }
+
+    /// Gets the length of the longest common prefix of the [`BoardId`]s' [bytes](AsRef<[u8]>).
+    ///
+    /// This is a cheap similarity measure, e.g. boards of the same vendor share at least the vendor.
+    pub fn common_prefix_len(&self, other: &BoardId) -> usize {
+        self.as_ref().iter().zip(other.as_ref()).take_while(|(a, b)| a == b).count()
+    }
+
+    /// Renders the [display form](Display) as a single line for syslog, truncated to at most
+    /// `max_len` characters with a trailing `...`.
+    ///
+    /// The display form is already printable ASCII, with control characters and non-ASCII bytes
+    /// escaped (e.g. a line feed as `\n`), so together with the truncation this guards logs
+    /// against injection and overly long lines from malformed DMI data.
+    pub fn syslog_line(&self, max_len: usize) -> String {
+        const ELLIPSIS: &str = "...";
+        let line = self.to_string();
+        if line.chars().count() <= max_len { return line }
+        let mut truncated = line.chars().take(max_len.saturating_sub(ELLIPSIS.len())).collect::<String>();
+        truncated.push_str(&ELLIPSIS[..max_len.min(ELLIPSIS.len())]);
+        truncated
+    }
+
+    /// Checks whether no part has been detected.
+    #[inline]
+    #[cfg(feature = "std")]
+    fn is_undetected(&self) -> bool { self.version == 0 }
+
+    /// Makes a [`BoardId`] of the bytes the function gives for each part, repacked into the
+    /// buffer. Empty parts are undetected.
+    ///
+    /// The function mustn't grow the parts of a [`BoardId`], so that the buffer can't overflow.
+    fn repack<I: IntoIterator<Item = u8>>(mut bytes: impl FnMut(Part) -> I) -> BoardId {
+        let mut buffer = [0u8; Self::BUFSZ];
+        let mut ends = [0u8; 3];
+        let mut n = 0;
+        for (end, part) in ends.iter_mut().zip(Part::ALL) {
+            for b in bytes(part) {
+                buffer[n] = b;
+                n += 1;
+            }
+            *end = n as u8;
+        }
+        Self { buffer, vendor: ends[0], name: ends[1], version: ends[2] }
+    }
+
+    /// The version byte leading the [stable binary encoding](Self::to_bytes).
+    const ENCODING_VERSION: u8 = 1;
+
+    /// The bytes the [stable binary encoding](Self::to_bytes) takes in addition to the parts: the
+    /// version byte and the length bytes.
+    pub(crate) const ENCODING_OVERHEAD: usize = 4;
+
+    /// Encodes the [`BoardId`] in a compact binary format that's stable across releases.
+    ///
+    /// The format is a version byte, followed by the vendor, name and version lengths (one byte
+    /// each), followed by the parts themselves. Undetected parts have a zero length.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let parts = &self.buffer[..self.version as usize];
+        let mut bytes = Vec::with_capacity(Self::ENCODING_OVERHEAD + parts.len());
+        bytes.extend([Self::ENCODING_VERSION, self.vendor, self.name - self.vendor, self.version - self.name]);
+        bytes.extend_from_slice(parts);
+        bytes
+    }
+
+    /// Gets the bytes the [`BoardId`] occupies when persisted minimally: the length of its
+    /// [stable binary encoding](Self::to_bytes), i.e. the combined length of the parts plus a fixed
+    /// 4-byte overhead.
+    ///
+    /// Unlike [`size_of`](core::mem::size_of), it doesn't count the unused buffer capacity.
+    pub fn heap_size(&self) -> usize { Self::ENCODING_OVERHEAD + self.version as usize }
+
+    /// Decodes a [`BoardId`] from its [stable binary encoding](Self::to_bytes).
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`] if the bytes aren't a valid encoding.
+    #[cfg(feature = "std")]
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let invalid = |msg| io::Error::new(io::ErrorKind::InvalidData, msg);
+        let Some((&[version, vendor, name, ver], parts)) = bytes.split_first_chunk::<4>() else { return Err(invalid("the board ID encoding is truncated")) };
+        if version != Self::ENCODING_VERSION { return Err(invalid("unknown board ID encoding version")) }
+        let total = vendor as usize + name as usize + ver as usize;
+        if total != parts.len() || total > Self::BUFSZ { return Err(invalid("the board ID encoding lengths don't match its data")) }
+        let mut buffer = [0u8; Self::BUFSZ];
+        buffer[..total].copy_from_slice(parts);
+        Ok(Self {
+            buffer,
+            vendor,
+               name: vendor + name,
+            version: vendor + name + ver,
+        })
+    }
+}
+
+/// A part of a [`BoardId`].
+#[derive(Debug, Hash, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
+pub enum Part {
+    /// The board's vendor / brand.
+    Vendor,
+    /// The board's name / model.
+    Name,
+    /// The board's version.
+    Version,
+}
+
+impl Part {
+    /// All the parts, in order.
+    pub const ALL: [Self; 3] = [Self::Vendor, Self::Name, Self::Version];
+
+    /// Gets the part's name: "vendor", "name" or "version".
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Vendor  => "vendor",
+            Self::Name    => "name",
+            Self::Version => "version",
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.name()) }
+}
+
+/// The detected parts, back to back.
+impl AsRef<[u8]> for BoardId {
+    #[inline]
+    fn as_ref(&self) -> &[u8] { &self.buffer[..self.version as usize] }
+}
+
+/// Makes a [`BoardId`] from up to three parts, in vendor, name, version order, e.g. as split by a
+/// deserializer.
+///
+/// Missing trailing parts are undetected, and so are empty parts, as per [`BoardId::from_parts`].
+/// Fails with [`io::ErrorKind::InvalidData`] if there are more than three parts, and with
+/// [`io::ErrorKind::WriteZero`] (from a [`CapacityError`]) if they don't fit in the buffer.
+#[cfg(feature = "std")]
+impl TryFrom<&[&[u8]]> for BoardId {
+    type Error = io::Error;
+
+    fn try_from(parts: &[&[u8]]) -> Result<Self, Self::Error> {
+        if parts.len() > 3 { return Err(io::Error::new(io::ErrorKind::InvalidData, "a board ID has at most three parts")) }
+        let part = |i: usize| parts.get(i).copied();
+        Ok(Self::from_parts(part(0), part(1), part(2))?)
+    }
+}
+
+/// Opens a file, returning `Ok(None)` if it doesn't exist.
+#[cfg(feature = "std")]
+fn open_existing_file(path: impl AsRef<Path>) -> io::Result<Option<File>> {
+    match File::open(path) {
+        Ok(file) => Ok(Some(file)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// The error for a [`BoardId`] that doesn't fit in its buffer.
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError {
+    /// The first part that doesn't fit.
+    pub part: Part,
+    /// The part's length (for readers, the bytes read before running out of room).
+    pub attempted: usize,
+    /// The room left in the buffer for the part, after the parts before it (for readers, less the
+    /// spare byte).
+    pub remaining: usize,
+}
+
+impl Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f, "the motherboard ID is abnormally large and doesn't fit in the buffer: its {} takes {} bytes, but only {} are left",
+            self.part, self.attempted, self.remaining,
+        )
+    }
+}
+
+impl core::error::Error for CapacityError {}
+
+/// Converts to an [`io::ErrorKind::WriteZero`] error.
+#[cfg(feature = "std")]
+impl From<CapacityError> for io::Error {
+    fn from(e: CapacityError) -> Self { io::Error::new(io::ErrorKind::WriteZero, e) }
+}
+
+/// How the trailing line terminator of each part is handled when reading.
+#[cfg(feature = "std")]
+#[derive(Debug, Default, Hash, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
+pub enum NewlinePolicy {
+    /// Strips a single trailing LF, as written by sysfs.
+    StripOne,
+    /// Strips every trailing CR and LF.
+    StripAll,
+    /// Strips a single trailing LF or CRLF.
+    #[default]
+    StripCrLf,
+    /// Keeps the part as read.
+    Keep,
+}
+
+#[cfg(feature = "std")]
+impl NewlinePolicy {
+    /// Gets the length of the part once its terminator is stripped according to the policy.
+    pub(crate) fn trimmed_len(self, part: &[u8]) -> usize {
+        match self {
+            Self::StripOne  => part.strip_suffix(b"\n").map_or(part.len(), <[u8]>::len),
+            Self::StripAll  => part.iter().rposition(|&b| b != b'\n' && b != b'\r').map_or(0, |i| i + 1),
+            Self::StripCrLf => part.strip_suffix(b"\r\n").or_else(|| part.strip_suffix(b"\n")).map_or(part.len(), <[u8]>::len),
+            Self::Keep      => part.len(),
+        }
+    }
 }
 
 /// Semi-opinionated Display implementation.
@@ -103,33 +589,38 @@ impl BoardId {
 /// All the [`MotherboardId`] field are assumed to be valid ASCII. Invalid ASCII characters will be
 /// escaped.
 impl Display for BoardId {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        let wrote_vendor = if let Some(vendor) = self.vendor() {
-            write!(f, "{} ", vendor.escape_ascii())?;
-            true
-        } else { false };
-
-        let detected_name = if let Some(name) = self.name() {
-            write!(f, "{}", name.escape_ascii())?;
-            true
-        } else if wrote_vendor {
-            write!(f, "motherboard")?;
-            false
-        } else {
-            return write!(f, "undetected motherboard");
-        };
-
-        if detected_name {
-            if let Some(version) = self.version() {
-                write!(f, " {}", version.escape_ascii())?;
-            }
-        }
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_parts(f, self.vendor(), self.name(), self.version())
+    }
+}
 
-        Ok(())
+/// Formats the parts as per [`BoardId`]'s [`Display`] implementation.
+fn fmt_parts(f: &mut fmt::Formatter, vendor: Option<&[u8]>, name: Option<&[u8]>, version: Option<&[u8]>) -> fmt::Result {
+    let wrote_vendor = if let Some(vendor) = vendor {
+        write!(f, "{} ", vendor.escape_ascii())?;
+        true
+    } else { false };
+
+    let detected_name = if let Some(name) = name {
+        write!(f, "{}", name.escape_ascii())?;
+        true
+    } else if wrote_vendor {
+        write!(f, "motherboard")?;
+        false
+    } else {
+        return write!(f, "undetected motherboard");
+    };
+
+    if detected_name {
+        if let Some(version) = version {
+            write!(f, " {}", version.escape_ascii())?;
+        }
     }
+
+    Ok(())
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod test {
     use super::*;
 
@@ -178,6 +669,45 @@ mod test {
             assert_eq!(result.version(), None                   );
         }
 
+        mod newline_policy {
+            use super::*;
+
+            fn name(policy: NewlinePolicy, input: &str) -> Option<Vec<u8>> {
+                BoardId::from_streams_with(policy, NOENT, Some(input.as_bytes()), NOENT).unwrap().name().map(<[u8]>::to_vec)
+            }
+
+            #[test]
+            fn strip_one() {
+                assert_eq!(name(NewlinePolicy::StripOne, "X\n\n").as_deref(), Some("X\n".as_bytes()));
+                assert_eq!(name(NewlinePolicy::StripOne, "X\r\n").as_deref(), Some("X\r".as_bytes()));
+            }
+
+            #[test]
+            fn strip_all() {
+                assert_eq!(name(NewlinePolicy::StripAll, "X\n\n").as_deref(), Some("X".as_bytes()));
+                assert_eq!(name(NewlinePolicy::StripAll, "X\r\n").as_deref(), Some("X".as_bytes()));
+            }
+
+            #[test]
+            fn strip_crlf() {
+                assert_eq!(name(NewlinePolicy::StripCrLf, "X\n\n").as_deref(), Some("X\n".as_bytes()));
+                assert_eq!(name(NewlinePolicy::StripCrLf, "X\r\n").as_deref(), Some("X"  .as_bytes()));
+            }
+
+            #[test]
+            fn keep() {
+                assert_eq!(name(NewlinePolicy::Keep, "X\n\n").as_deref(), Some("X\n\n".as_bytes()));
+                assert_eq!(name(NewlinePolicy::Keep, "X\r\n").as_deref(), Some("X\r\n".as_bytes()));
+            }
+
+            #[test]
+            fn no_terminator() {
+                for policy in [NewlinePolicy::StripOne, NewlinePolicy::StripAll, NewlinePolicy::StripCrLf, NewlinePolicy::Keep] {
+                    assert_eq!(name(policy, "X").as_deref(), Some("X".as_bytes()));
+                }
+            }
+        }
+
         mod buffer {
             use super::*;
 
@@ -187,6 +717,25 @@ mod test {
                 assert_eq!(name.len(), BoardId::BUFSZ - 1, "bad test");
                 let e = BoardId::from_streams(NOENT, Some(format!("{name}\n").as_bytes()), NOENT).unwrap_err();
                 assert_eq!(e.kind(), io::ErrorKind::WriteZero);
+                let e = e.get_ref().unwrap().downcast_ref::<CapacityError>().unwrap();
+                assert_eq!(*e, CapacityError { part: Part::Name, attempted: 255, remaining: 254 });
+            }
+
+            #[test]
+            fn each_part_too_large() {
+                let (fill, large) = ([b'X'; 100], [b'X'; 200]);
+                for (part, parts) in [
+                    (Part::Vendor , [Some(&[b'X'; 256][..]), None, None]),
+                    (Part::Name   , [Some(&fill[..]), Some(&large[..]), None]),
+                    (Part::Version, [Some(&fill[..]), Some(&fill[..]), Some(&fill[..])]),
+                ] {
+                    let e = BoardId::from_parts(parts[0], parts[1], parts[2]).unwrap_err();
+                    let remaining = BoardId::BUFSZ - parts[..part as usize].iter().map(|p| p.unwrap().len()).sum::<usize>();
+                    assert_eq!(e, CapacityError { part, attempted: parts[part as usize].unwrap().len(), remaining });
+                    let streamed = BoardId::from_streams(parts[0], parts[1], parts[2]).unwrap_err();
+                    let streamed = *streamed.get_ref().unwrap().downcast_ref::<CapacityError>().unwrap();
+                    assert_eq!(streamed, CapacityError { part, attempted: remaining, remaining: remaining - 1 });
+                }
             }
 
             #[test]
@@ -201,6 +750,48 @@ mod test {
         }
     }
 
+    mod detect_with_lengths {
+        use super::*;
+        use crate::fixture::TempDir;
+
+        #[test]
+        fn double_newline() {
+            let root = TempDir::new("lengths");
+            root.write("sys/class/dmi/id/board_vendor", "VENDOR\n\n");
+            root.write("sys/class/dmi/id/board_name", "NAME");
+            let (board, lengths) = BoardId::detect_with_lengths_at(root.path()).unwrap();
+            assert_eq!(board.vendor(), Some(&b"VENDOR\n"[..]));
+            assert_eq!(lengths, [(8, 7), (4, 4), (0, 0)]);
+        }
+    }
+
+    mod from_opt_strings {
+        use super::*;
+
+        #[test]
+        fn full() {
+            let board = BoardId::from_opt_strings(Some("VENDOR".into()), Some("NAME".into()), Some("VERSION".into())).unwrap();
+            assert_eq!( board.vendor(), Some("VENDOR" .as_bytes()));
+            assert_eq!(   board.name(), Some("NAME"   .as_bytes()));
+            assert_eq!(board.version(), Some("VERSION".as_bytes()));
+        }
+
+        #[test]
+        fn partial() {
+            let board = BoardId::from_opt_strings(None, Some("NAME".into()), Some(String::new())).unwrap();
+            assert_eq!( board.vendor(), None                   );
+            assert_eq!(   board.name(), Some("NAME".as_bytes()));
+            assert_eq!(board.version(), None                   );
+        }
+
+        #[test]
+        fn too_large() {
+            let e = BoardId::from_opt_strings(Some("V".repeat(100)), Some("N".repeat(100)), Some("R".repeat(56))).unwrap_err();
+            assert_eq!(e, CapacityError { part: Part::Version, attempted: 56, remaining: 55 });
+            assert!(BoardId::from_opt_strings(Some("V".repeat(100)), Some("N".repeat(100)), Some("R".repeat(55))).is_ok());
+        }
+    }
+
     mod format {
         use super::*;
 
@@ -240,4 +831,176 @@ mod test {
             assert_eq!(board.to_string(), "NAME");
         }
     }
+
+    mod try_from_slices {
+        use super::*;
+
+        #[test]
+        fn two() {
+            let board = BoardId::try_from(&[&b"VENDOR"[..], b"NAME"][..]).unwrap();
+            assert_eq!(board.as_tuple(), (Some(&b"VENDOR"[..]), Some(&b"NAME"[..]), None));
+            assert!(BoardId::try_from(&[][..]).unwrap().is_undetected());
+        }
+
+        #[test]
+        fn three() {
+            let board = BoardId::try_from(&[&b"VENDOR"[..], b"", b"VERSION"][..]).unwrap();
+            assert_eq!(board.as_tuple(), (Some(&b"VENDOR"[..]), None, Some(&b"VERSION"[..])));
+        }
+
+        #[test]
+        fn too_many() {
+            let e = BoardId::try_from(&[&b"VENDOR"[..], b"NAME", b"VERSION", b"EXTRA"][..]).unwrap_err();
+            assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+            let e = BoardId::try_from(&[&[b'X'; 256][..]][..]).unwrap_err();
+            assert_eq!(e.kind(), io::ErrorKind::WriteZero);
+        }
+    }
+
+    mod as_tuple {
+        use super::*;
+
+        #[test]
+        fn match_arms() {
+            let kind = |board: BoardId| match board.as_tuple() {
+                (Some(b"ASUS"), Some(name), _) if name.starts_with(b"PRIME") => "asus prime",
+                (Some(b"ASUS"), _, _) => "asus",
+                (None, None, None) => "undetected",
+                _ => "other",
+            };
+            assert_eq!(kind(BoardId::from_parts(Some(b"ASUS"), Some(b"PRIME B550"), Some(b"1.0")).unwrap()), "asus prime");
+            assert_eq!(kind(BoardId::from_parts(Some(b"ASUS"), None, None).unwrap()), "asus");
+            assert_eq!(kind(BoardId::from_parts(None, None, None).unwrap()), "undetected");
+            assert_eq!(kind(BoardId::from_parts(Some(b"MSI"), None, None).unwrap()), "other");
+        }
+    }
+
+    mod merge {
+        use super::*;
+
+        #[test]
+        fn complementary() {
+            let sysfs     = BoardId::from_parts(Some(b"VENDOR"), Some(b"NAME"), None).unwrap();
+            let dmidecode = BoardId::from_parts(None, None, Some(b"VERSION")).unwrap();
+            let merged = sysfs.merge(&dmidecode).unwrap();
+            assert_eq!(merged, BoardId::from_parts(Some(b"VENDOR"), Some(b"NAME"), Some(b"VERSION")).unwrap());
+            assert_eq!(dmidecode.merge(&sysfs).unwrap(), merged);
+        }
+
+        #[test]
+        fn prefers_self() {
+            let a = BoardId::from_parts(Some(b"A"), None, None).unwrap();
+            let b = BoardId::from_parts(Some(b"B"), Some(b"NAME"), None).unwrap();
+            assert_eq!(a.merge(&b).unwrap().vendor(), Some("A".as_bytes()));
+            assert_eq!(a.merge(&b).unwrap().name(), Some("NAME".as_bytes()));
+        }
+
+        #[test]
+        fn too_large() {
+            let large = [b'X'; 128];
+            let a = BoardId::from_parts(Some(&large), None, None).unwrap();
+            let b = BoardId::from_parts(None, Some(&large), None).unwrap();
+            assert_eq!(a.merge(&b), Err(CapacityError { part: Part::Name, attempted: 128, remaining: 127 }));
+        }
+    }
+
+    mod hash_logical {
+        use std::hash::DefaultHasher;
+
+        use super::*;
+
+        fn hash(vendor: &str, name: &str) -> u64 {
+            let board = BoardId::from_streams(Some(vendor.as_bytes()), Some(name.as_bytes()), NOENT).unwrap();
+            let mut hasher = DefaultHasher::new();
+            board.hash_logical(&mut hasher);
+            hasher.finish()
+        }
+
+        #[test]
+        fn equal() {
+            assert_eq!(hash("VENDOR\n", "NAME\n"), hash("VENDOR\n", "NAME\n"));
+            assert_eq!(hash("VENDOR\n", "NAME\n"), hash("VENDOR", "NAME"));
+        }
+
+        #[test]
+        fn unequal() {
+            assert_ne!(hash("VENDOR\n", "NAME\n"), hash("VENDOR\n", "OTHER\n"));
+            assert_ne!(hash("AB\n", "C\n"), hash("A\n", "BC\n"));
+        }
+    }
+
+    mod common_prefix_len {
+        use super::*;
+
+        #[test]
+        fn same_vendor() {
+            let a = BoardId::from_streams(Some("VENDOR\n".as_bytes()), Some("NAME-A\n".as_bytes()), NOENT).unwrap();
+            let b = BoardId::from_streams(Some("VENDOR\n".as_bytes()), Some("NAME-B\n".as_bytes()), NOENT).unwrap();
+            assert_eq!(a.as_ref(), b"VENDORNAME-A");
+            assert_eq!(a.common_prefix_len(&b), "VENDORNAME-".len());
+            assert_eq!(a.common_prefix_len(&a), "VENDORNAME-A".len());
+        }
+
+        #[test]
+        fn disjoint() {
+            let a = BoardId::from_streams(Some("VENDOR\n".as_bytes()), NOENT, NOENT).unwrap();
+            let b = BoardId::from_streams(Some("OTHER\n".as_bytes()), NOENT, NOENT).unwrap();
+            assert_eq!(a.common_prefix_len(&b), 0);
+        }
+    }
+
+    mod syslog_line {
+        use super::*;
+
+        #[test]
+        fn short() {
+            let board = BoardId::from_parts(Some(b"VENDOR"), Some(b"NAME"), None).unwrap();
+            assert_eq!(board.syslog_line(80), "VENDOR NAME");
+            assert_eq!(board.syslog_line(11), "VENDOR NAME");
+        }
+
+        #[test]
+        fn truncated() {
+            let board = BoardId::from_parts(Some(b"VENDOR"), Some(b"NAME"), None).unwrap();
+            assert_eq!(board.syslog_line(10), "VENDOR ...");
+            assert_eq!(board.syslog_line(2), "..");
+            assert_eq!(board.syslog_line(0), "");
+        }
+
+        #[test]
+        fn control() {
+            let board = BoardId::from_parts(Some(b"VEN\nDOR"), Some(b"NA\x1bME"), None).unwrap();
+            let line = board.syslog_line(80);
+            assert!(!line.chars().any(char::is_control), "{line}");
+            assert_eq!(line, "VEN\\nDOR NA\\x1bME");
+        }
+    }
+
+    mod encoding {
+        use super::*;
+
+        #[test]
+        fn round_trip() {
+            let board = BoardId::from_streams(Some("VENDOR\n".as_bytes()), NOENT, Some("VERSION\n".as_bytes())).unwrap();
+            let bytes = board.to_bytes();
+            assert_eq!(bytes, b"\x01\x06\x00\x07VENDORVERSION");
+            assert_eq!(board.heap_size(), 17);
+            assert_eq!(board.heap_size(), bytes.len());
+            assert_eq!(BoardId::from_bytes(&bytes).unwrap(), board);
+        }
+
+        #[test]
+        fn undetected() {
+            let board = BoardId::from_streams(NOENT, NOENT, NOENT).unwrap();
+            assert_eq!(BoardId::from_bytes(&board.to_bytes()).unwrap(), board);
+            assert_eq!(board.heap_size(), 4);
+        }
+
+        #[test]
+        fn invalid() {
+            for bytes in [&b""[..], b"\x01\x01", b"\x02\x00\x00\x00", b"\x01\x01\x01\x01AB", b"\x01\x00\x01\x00AB", b"\x01\xff\xff\xff"] {
+                assert_eq!(BoardId::from_bytes(bytes).unwrap_err().kind(), io::ErrorKind::InvalidData, "{bytes:?}");
+            }
+        }
+    }
 }
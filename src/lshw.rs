@@ -0,0 +1,152 @@
+//! Parsing [`BoardId`]s out of `lshw -json` output.
+
+use std::{fmt::{self, Display}, io};
+
+use crate::{json::Value, BoardId, CapacityError};
+
+/// The error for [`BoardId::from_lshw_json`].
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub enum LshwError {
+    /// The document isn't valid JSON (or is nested too deeply).
+    Invalid,
+    /// The document has neither a motherboard node nor a top-level system node.
+    NotFound,
+    /// The parts don't fit in a [`BoardId`].
+    Capacity(CapacityError),
+}
+
+impl Display for LshwError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Invalid     => write!(f, "the lshw document isn't valid JSON"),
+            Self::NotFound    => write!(f, "the motherboard wasn't found in the lshw document"),
+            Self::Capacity(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for LshwError {}
+
+/// Converts to an [`io::ErrorKind::InvalidData`], [`io::ErrorKind::NotFound`] or
+/// [`io::ErrorKind::WriteZero`] error, respectively.
+impl From<LshwError> for io::Error {
+    fn from(e: LshwError) -> Self {
+        let kind = match e {
+            LshwError::Invalid     => io::ErrorKind::InvalidData,
+            LshwError::NotFound    => io::ErrorKind::NotFound,
+            LshwError::Capacity(_) => io::ErrorKind::WriteZero,
+        };
+        io::Error::new(kind, e)
+    }
+}
+
+/// Finds the first node, in document order, that's the motherboard: of class `bus` and described
+/// as `Motherboard`.
+fn find_motherboard(node: &Value) -> Option<&Value> {
+    let is_motherboard = node.get("class").and_then(Value::as_str) == Some("bus")
+        && node.get("description").and_then(Value::as_str) == Some("Motherboard");
+    if is_motherboard { return Some(node) }
+    match node {
+        Value::Array(nodes) => nodes.iter().find_map(find_motherboard),
+        _ => match node.get("children")? {
+            Value::Array(children) => children.iter().find_map(find_motherboard),
+            _ => None,
+        },
+    }
+}
+
+impl BoardId {
+    /// Parses the [`BoardId`] out of `lshw -json` output, from the `vendor`, `product` and
+    /// `version` of its motherboard node (of class `bus`, described as `Motherboard`), falling
+    /// back to the top-level system node.
+    ///
+    /// Both the object top level of newer lshw versions and the array of older ones are accepted.
+    /// Members that are missing or not strings are undetected, and other members are ignored.
+    pub fn from_lshw_json(json: &str) -> Result<Self, LshwError> {
+        let document = Value::parse(json).ok_or(LshwError::Invalid)?;
+        let system = match &document {
+            Value::Array(nodes) => nodes.first(),
+            node => Some(node),
+        }.filter(|node| node.get("class").and_then(Value::as_str) == Some("system"));
+        let node = find_motherboard(&document).or(system).ok_or(LshwError::NotFound)?;
+        let part = |key| node.get(key).and_then(Value::as_str).map(str::as_bytes);
+        Self::from_parts(part("vendor"), part("product"), part("version")).map_err(LshwError::Capacity)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Output of lshw B.02.19 (object top level).
+    const LSHW_B0219: &str = r#"{
+  "id" : "desktop",
+  "class" : "system",
+  "claimed" : true,
+  "handle" : "DMI:0001",
+  "description" : "Desktop Computer",
+  "product" : "System Product Name (SKU)",
+  "vendor" : "System manufacturer",
+  "version" : "System Version",
+  "width" : 64,
+  "configuration" : { "boot" : "normal", "chassis" : "desktop" },
+  "capabilities" : { "smbios-3.3.0" : "SMBIOS version 3.3.0", "vsyscall32" : "32-bit processes" },
+  "children" : [
+    {
+      "id" : "core",
+      "class" : "bus",
+      "claimed" : true,
+      "handle" : "DMI:0002",
+      "description" : "Motherboard",
+      "product" : "PRIME B550-PLUS",
+      "vendor" : "ASUSTeK COMPUTER INC.",
+      "physid" : "0",
+      "version" : "Rev X.0x",
+      "serial" : "201075725201234",
+      "slot" : "Default string",
+      "children" : [
+        { "id" : "memory", "class" : "memory", "claimed" : true, "size" : 34359738368, "units" : "bytes" }
+      ]
+    }
+  ]
+}"#;
+
+    /// Output of lshw B.02.18 (array top level), of a board whose motherboard node has no
+    /// description.
+    const LSHW_B0218: &str = r#"[
+{
+  "id" : "thinkpad",
+  "class" : "system",
+  "claimed" : true,
+  "description" : "Notebook",
+  "product" : "20XWCTO1WW (LENOVO_MT_20XW_BU_Think_FM_ThinkPad T14 Gen 2i)",
+  "vendor" : "LENOVO",
+  "version" : "ThinkPad T14 Gen 2i",
+  "width" : 64,
+  "children" : [
+    { "id" : "core", "class" : "bus", "claimed" : true, "product" : "20XWCTO1WW", "vendor" : "LENOVO", "physid" : "0" }
+  ]
+}
+]"#;
+
+    #[test]
+    fn motherboard() {
+        let board = BoardId::from_lshw_json(LSHW_B0219).unwrap();
+        assert_eq!(board, BoardId::from_parts(Some(b"ASUSTeK COMPUTER INC."), Some(b"PRIME B550-PLUS"), Some(b"Rev X.0x")).unwrap());
+    }
+
+    #[test]
+    fn system_fallback() {
+        let board = BoardId::from_lshw_json(LSHW_B0218).unwrap();
+        assert_eq!(board.as_tuple(), (Some(&b"LENOVO"[..]), Some(&b"20XWCTO1WW (LENOVO_MT_20XW_BU_Think_FM_ThinkPad T14 Gen 2i)"[..]), Some(&b"ThinkPad T14 Gen 2i"[..])));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(BoardId::from_lshw_json(r#"{"id": "cpu", "class": "processor"}"#), Err(LshwError::NotFound));
+        assert_eq!(BoardId::from_lshw_json("[]"), Err(LshwError::NotFound));
+        assert_eq!(BoardId::from_lshw_json(r#"{"id": "desktop", "children": [}"#), Err(LshwError::Invalid));
+        assert_eq!(BoardId::from_lshw_json(&"[".repeat(1000)), Err(LshwError::Invalid), "unbounded nesting");
+        assert_eq!(io::Error::from(LshwError::NotFound).kind(), io::ErrorKind::NotFound);
+    }
+}
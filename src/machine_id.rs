@@ -0,0 +1,71 @@
+//! [`machine_id`], a stable host identifier.
+
+use std::{fs, hash::Hasher, io, path::Path};
+
+use crate::{fingerprint::Fnv1a, BoardId};
+
+/// The path of the systemd machine ID, relative to the filesystem root.
+const MACHINE_ID: &str = "etc/machine-id";
+
+/// Gets a host identifier combining the [`BoardId`] with `/etc/machine-id`.
+///
+/// See [`machine_id_at`].
+pub fn machine_id() -> io::Result<String> { machine_id_at("/") }
+
+/// Gets a host identifier of the system whose filesystem is rooted at the given path, combining
+/// its [`BoardId`] with its `etc/machine-id`.
+///
+/// The identifier is the 64-bit FNV-1a hash, as 16 lowercase hexadecimal digits, of the board's
+/// [fingerprint](BoardId::fingerprint) (as little-endian bytes) followed by the trimmed machine
+/// ID. When the machine ID is missing or unreadable, it's the hash of the fingerprint alone. The
+/// hash is stable across releases, but not cryptographic, and it changes when either input does.
+///
+/// Fails if detection fails.
+pub fn machine_id_at(root: impl AsRef<Path>) -> io::Result<String> {
+    let root = root.as_ref();
+    let board = BoardId::detect_at(root)?;
+    let machine_id = fs::read(root.join(MACHINE_ID)).ok();
+    Ok(combine(&board, machine_id.as_deref()))
+}
+
+/// Combines the [`BoardId`] and the machine ID into a host identifier, as per [`machine_id_at`].
+fn combine(board: &BoardId, machine_id: Option<&[u8]>) -> String {
+    let mut hasher = Fnv1a::default();
+    hasher.write(&board.fingerprint().to_le_bytes());
+    if let Some(machine_id) = machine_id { hasher.write(machine_id.trim_ascii()) }
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    fn board() -> BoardId { BoardId::from_parts(Some(b"VENDOR"), Some(b"NAME"), None).unwrap() }
+
+    #[test]
+    fn combined() {
+        let id = combine(&board(), Some(b"0123456789abcdef0123456789abcdef\n"));
+        assert_eq!(id.len(), 16);
+        assert_eq!(id, combine(&board(), Some(b"0123456789abcdef0123456789abcdef")));
+        assert_ne!(id, combine(&board(), Some(b"fedcba9876543210fedcba9876543210")));
+        assert_ne!(id, combine(&BoardId::from_parts(Some(b"OTHER"), None, None).unwrap(), Some(b"0123456789abcdef0123456789abcdef")));
+    }
+
+    #[test]
+    fn fallback() {
+        let mut hasher = Fnv1a::default();
+        hasher.write(&board().fingerprint().to_le_bytes());
+        assert_eq!(combine(&board(), None), format!("{:016x}", hasher.finish()));
+    }
+
+    #[test]
+    fn at_root() {
+        let root = TempDir::new("machine-id");
+        root.write("sys/class/dmi/id/board_vendor", "VENDOR\n");
+        root.write("sys/class/dmi/id/board_name", "NAME\n");
+        assert_eq!(machine_id_at(root.path()).unwrap(), combine(&board(), None));
+        root.write("etc/machine-id", "0123456789abcdef0123456789abcdef\n");
+        assert_eq!(machine_id_at(root.path()).unwrap(), combine(&board(), Some(b"0123456789abcdef0123456789abcdef")));
+    }
+}
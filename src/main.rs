@@ -1 +1,270 @@
-fn main() { println!("{}", board_id::BoardId::detect().unwrap()); }
+//! Reference command line interface for [`board_id`].
+
+use std::{fs, io::{self, Write}, process::ExitCode};
+
+use board_id::{BoardId, Part, Rule};
+
+/// The usage message.
+const USAGE: &str = "\
+Usage: board_id [OPTIONS]
+       board_id [--match-vendor <GLOB>] [--match-name <GLOB>] [--match-version <GLOB>]
+                [--match-file <PATH>] [--verbose]
+
+Prints the motherboard ID, or checks it against glob patterns.
+
+Options:
+  --part <PART>            Print only the given part: vendor, name or version
+  --all                    Print every part on its own line (empty if undetected)
+  --json                   Print the parts as a JSON object (null if undetected)
+  --explain                Print the paths detection would read, in order, and exit
+  --zero                   Terminate output records with NUL instead of newline
+  --match-vendor <GLOB>    Match the vendor against the glob (`*` and `?` wildcards)
+  --match-name <GLOB>      Match the name against the glob
+  --match-version <GLOB>   Match the version against the glob
+  --match-file <PATH>      Match against the rules in the file, one `VENDOR|NAME|VERSION`
+                           rule per line (blank lines and `#` comments are ignored)
+  --verbose                In match mode, print whether the board matched
+  -h, --help               Print this message
+
+The --match-* options together form one rule, and each line of the match file is
+another; the board matches if it matches any rule. Undetected parts match as empty.
+
+Exit codes:
+  0   At least one part was detected / the board matched
+  1   No part was detected / the board didn't match
+  2   Detection failed (or the match file couldn't be read)
+  64  Invalid usage
+";
+
+/// Exit code when no part was detected.
+const EXIT_UNDETECTED: u8 = 1;
+/// Exit code when the board didn't match, in match mode.
+const EXIT_NO_MATCH: u8 = 1;
+/// Exit code when detection failed.
+const EXIT_ERROR: u8 = 2;
+/// Exit code for invalid usage (`EX_USAGE`).
+const EXIT_USAGE: u8 = 64;
+
+/// What to print.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum Output { #[default] Display, Part(Part), All, Json, Explain }
+
+/// The command line arguments.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Args {
+    /// What to print.
+    output: Output,
+    /// Whether records are NUL-terminated.
+    zero: bool,
+    /// The rule made of the `--match-*` patterns, if any were given.
+    rule: Option<Rule>,
+    /// The match file.
+    match_file: Option<String>,
+    /// Whether to print the match result.
+    verbose: bool,
+}
+
+impl Args {
+    /// Checks whether the arguments are for match mode.
+    fn is_match(&self) -> bool { self.rule.is_some() || self.match_file.is_some() }
+
+    /// Parses the arguments, returning `Ok(None)` if help was requested.
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut parsed = Self::default();
+        let set_output = |output: Output, parsed: &mut Self| {
+            if parsed.output != Output::Display { return Err("only one of --part, --all, --json and --explain may be given".to_owned()) }
+            parsed.output = output;
+            Ok(())
+        };
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("{arg} requires a value"));
+            match arg.as_str() {
+                "--match-vendor"  => parsed.rule.get_or_insert_default().vendor  = Some(value()?.into_bytes()),
+                "--match-name"    => parsed.rule.get_or_insert_default().name    = Some(value()?.into_bytes()),
+                "--match-version" => parsed.rule.get_or_insert_default().version = Some(value()?.into_bytes()),
+                "--match-file"    => parsed.match_file = Some(value()?),
+                "--verbose"       => parsed.verbose = true,
+                "--part" => {
+                    let part = value()?;
+                    let part = Part::ALL.into_iter().find(|p| p.name() == part).ok_or_else(|| format!("unknown part \"{part}\""))?;
+                    set_output(Output::Part(part), &mut parsed)?;
+                }
+                "--all"  => set_output(Output::All , &mut parsed)?,
+                "--json" => set_output(Output::Json, &mut parsed)?,
+                "--explain" => set_output(Output::Explain, &mut parsed)?,
+                "--zero" => parsed.zero = true,
+                "-h" | "--help" => return Ok(None),
+                _ => return Err(format!("unexpected argument \"{arg}\"")),
+            }
+        }
+        if parsed.is_match() && parsed.output != Output::Display { return Err("match mode can't be combined with --part, --all, --json or --explain".to_owned()) }
+        Ok(Some(parsed))
+    }
+
+    /// Gets the rules to match against, reading the match file if given.
+    fn rules(&self) -> io::Result<Vec<Rule>> {
+        let mut rules = Vec::from_iter(self.rule.clone());
+        if let Some(path) = &self.match_file {
+            for line in fs::read_to_string(path)?.lines() {
+                if line.trim().is_empty() || line.trim_start().starts_with('#') { continue }
+                rules.push(line.parse()?);
+            }
+        }
+        Ok(rules)
+    }
+}
+
+/// Writes the paths detection would read, each followed by the terminator.
+fn write_explain(mut w: impl Write, terminator: &[u8]) -> io::Result<()> {
+    for path in board_id::probe_paths() {
+        w.write_all(path.as_os_str().as_encoded_bytes())?;
+        w.write_all(terminator)?;
+    }
+    Ok(())
+}
+
+/// Writes the output for the board.
+fn write_output(mut w: impl Write, args: &Args, board: &BoardId) -> io::Result<()> {
+    let terminator: &[u8] = if args.zero { b"\0" } else { b"\n" };
+    match args.output {
+        Output::Display    => write!(w, "{board}")?,
+        Output::Part(part) => w.write_all(board.part(part).unwrap_or_default())?,
+        Output::Json       => w.write_all(board.to_json().as_bytes())?,
+        Output::All        => {
+            for part in Part::ALL {
+                w.write_all(board.part(part).unwrap_or_default())?;
+                w.write_all(terminator)?;
+            }
+            return Ok(())
+        }
+        Output::Explain    => return write_explain(w, terminator),
+    }
+    w.write_all(terminator)
+}
+
+fn main() -> ExitCode {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("board_id: {e}\n\n{USAGE}");
+            return ExitCode::from(EXIT_USAGE);
+        }
+    };
+    let rules = match args.rules() {
+        Ok(rules) => rules,
+        Err(e) => {
+            eprintln!("board_id: failed to read the match file: {e}");
+            return ExitCode::from(EXIT_ERROR);
+        }
+    };
+    if args.output == Output::Explain {
+        // no detection, so the paths are explained even when detection fails
+        return match write_explain(io::stdout().lock(), if args.zero { b"\0" } else { b"\n" }) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("board_id: {e}");
+                ExitCode::from(EXIT_ERROR)
+            }
+        };
+    }
+    let board = match BoardId::detect() {
+        Ok(board) => board,
+        Err(e) => {
+            eprintln!("board_id: detection failed: {e}");
+            return ExitCode::from(EXIT_ERROR);
+        }
+    };
+    if args.is_match() {
+        let matched = rules.iter().any(|rule| rule.matches(&board));
+        if args.verbose { println!("{}: {board}", if matched { "match" } else { "no match" }) }
+        return if matched { ExitCode::SUCCESS } else { ExitCode::from(EXIT_NO_MATCH) };
+    }
+    if let Err(e) = write_output(io::stdout().lock(), &args, &board) {
+        eprintln!("board_id: {e}");
+        return ExitCode::from(EXIT_ERROR);
+    }
+    let detected = Part::ALL.into_iter().any(|part| board.part(part).is_some());
+    if detected { ExitCode::SUCCESS } else { ExitCode::from(EXIT_UNDETECTED) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<Args>, String> {
+        Args::parse(args.iter().map(|&arg| arg.to_owned()))
+    }
+
+    fn output(args: &[&str], board: &BoardId) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_output(&mut out, &parse(args).unwrap().unwrap(), board).unwrap();
+        out
+    }
+
+    fn board() -> BoardId {
+        BoardId::from_parts(Some(b"VENDOR"), None, Some(b"VER\"SION")).unwrap()
+    }
+
+    #[test]
+    fn args() {
+        assert_eq!(parse(&[]), Ok(Some(Args::default())));
+        assert_eq!(parse(&["--part", "name", "--zero"]), Ok(Some(Args { output: Output::Part(Part::Name), zero: true, ..Args::default() })));
+        assert_eq!(parse(&["--help"]), Ok(None));
+        assert!(parse(&["--part"]).is_err());
+        assert!(parse(&["--part", "serial"]).is_err());
+        assert!(parse(&["--json", "--all"]).is_err());
+        assert!(parse(&["--bogus"]).is_err());
+    }
+
+    #[test]
+    fn match_args() {
+        let args = parse(&["--match-vendor", "ASUS*", "--match-name", "*B550*", "--verbose"]).unwrap().unwrap();
+        assert_eq!(args.rule, Some("ASUS*|*B550*".parse().unwrap()));
+        assert!(args.is_match() && args.verbose);
+        assert!(parse(&["--match-name"]).is_err());
+        assert!(parse(&["--match-name", "*", "--json"]).is_err());
+    }
+
+    #[test]
+    fn match_file() {
+        let path = std::env::temp_dir().join(format!("board_id-{}-match-file", std::process::id()));
+        fs::write(&path, "# golden boards\nASUS*|*B550*\n\n|PRIME*\n").unwrap();
+        let args = parse(&["--match-version", "1.*", "--match-file", path.to_str().unwrap()]).unwrap().unwrap();
+        let rules = args.rules();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(rules.unwrap(), ["||1.*", "ASUS*|*B550*", "|PRIME*"].map(|rule| rule.parse::<Rule>().unwrap()));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(output(&[], &board()), b"VENDOR motherboard\n");
+    }
+
+    #[test]
+    fn part() {
+        assert_eq!(output(&["--part", "vendor"], &board()), b"VENDOR\n");
+        assert_eq!(output(&["--part", "name"  ], &board()), b"\n");
+    }
+
+    #[test]
+    fn all() {
+        assert_eq!(output(&["--all"          ], &board()), b"VENDOR\n\nVER\"SION\n");
+        assert_eq!(output(&["--all", "--zero"], &board()), b"VENDOR\0\0VER\"SION\0");
+    }
+
+    #[test]
+    fn explain() {
+        assert!(output(&["--explain"], &board()).starts_with(b"/sys/class/dmi/id/board_vendor\n"));
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(output(&["--json"], &board()), br#"{"vendor":"VENDOR","name":null,"version":"VER\"SION"}
+"#);
+    }
+}
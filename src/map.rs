@@ -0,0 +1,70 @@
+//! Making [`BoardId`]s from key/value maps of collected DMI data.
+
+use std::{collections::HashMap, io};
+
+use crate::{BoardId, DmiField, NewlinePolicy, Part};
+
+/// The friendly keys of each part, besides its sysfs attribute name, in order of precedence.
+const FRIENDLY_KEYS: [(Part, &[&str]); 3] = [
+    (Part::Vendor , &["vendor", "manufacturer"]),
+    (Part::Name   , &["name", "product", "model"]),
+    (Part::Version, &["version"]),
+];
+
+/// Gets the part of the key, and its precedence (lower is preferred), or `None` if it's unknown.
+fn key_part(key: &str) -> Option<(Part, usize)> {
+    let sysfs = [(Part::Vendor, DmiField::BoardVendor), (Part::Name, DmiField::BoardName), (Part::Version, DmiField::BoardVersion)];
+    if let Some(&(part, _)) = sysfs.iter().find(|(_, field)| field.attribute() == key) { return Some((part, 0)) }
+    FRIENDLY_KEYS.iter().find_map(|&(part, keys)| keys.iter().position(|&k| k == key).map(|i| (part, i + 1)))
+}
+
+impl BoardId {
+    /// Attempts to make a [`BoardId`] from a map of collected DMI data.
+    ///
+    /// The keys are either the sysfs attribute names (`board_vendor`, `board_name`,
+    /// `board_version`) or friendly ones:
+    ///
+    /// - vendor: `vendor`, `manufacturer`
+    /// - name: `name`, `product`, `model`
+    /// - version: `version`
+    ///
+    /// Unknown keys are ignored, the sysfs names take precedence over the friendly ones, and the
+    /// friendly ones take precedence in the order listed. The
+    /// values are trimmed, and limited in size, like the sysfs attributes (see [`Self::detect`]).
+    pub fn from_map<K: AsRef<str>, V: AsRef<[u8]>, S>(map: &HashMap<K, V, S>) -> io::Result<Self> {
+        let mut parts: [Option<(&[u8], usize)>; 3] = [None; 3];
+        for (key, value) in map {
+            let Some((part, precedence)) = key_part(key.as_ref()) else { continue };
+            let slot = &mut parts[part as usize];
+            if slot.is_none_or(|(_, slot_precedence)| precedence < slot_precedence) { *slot = Some((value.as_ref(), precedence)) }
+        }
+        let [vendor, name, version] = parts.map(|part| part.map(|(value, _)| &value[..NewlinePolicy::default().trimmed_len(value)]));
+        Ok(Self::from_parts(vendor, name, version)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn keys() {
+        let map = HashMap::from([("manufacturer", "VENDOR\n"), ("board_name", "NAME\n"), ("version", "VERSION"), ("serial", "SERIAL")]);
+        assert_eq!(BoardId::from_map(&map).unwrap(), BoardId::from_parts(Some(b"VENDOR"), Some(b"NAME"), Some(b"VERSION")).unwrap());
+        assert_eq!(BoardId::from_map(&HashMap::<String, Vec<u8>>::new()).unwrap(), BoardId::from_parts(None, None, None).unwrap());
+    }
+
+    #[test]
+    fn sysfs_preferred() {
+        let map = HashMap::from([("vendor", "FRIENDLY"), ("board_vendor", "SYSFS"), ("manufacturer", "FRIENDLIER")]);
+        assert_eq!(BoardId::from_map(&map).unwrap().vendor(), Some(&b"SYSFS"[..]));
+        let map = HashMap::from([("manufacturer", "SECOND"), ("vendor", "FIRST")]);
+        assert_eq!(BoardId::from_map(&map).unwrap().vendor(), Some(&b"FIRST"[..]));
+    }
+
+    #[test]
+    fn capacity() {
+        let map = HashMap::from([("name", "N".repeat(BoardId::BUFSZ + 1))]);
+        assert!(BoardId::from_map(&map).is_err());
+    }
+}
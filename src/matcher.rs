@@ -0,0 +1,124 @@
+//! [`BoardMatcher`], for ranking known [`BoardId`]s by their similarity to a query.
+
+use alloc::{collections::BTreeSet, string::String, vec::Vec};
+
+use crate::{similarity::jaccard, BoardId};
+
+/// A known board in a [`BoardMatcher`], along with the caller's value for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatcherEntry<T> {
+    /// The board.
+    board: BoardId,
+    /// The caller's value.
+    value: T,
+    /// The board's [tags](BoardId::tags), computed once.
+    tags: BTreeSet<String>,
+}
+
+impl<T> MatcherEntry<T> {
+    /// Gets the board.
+    pub fn board(&self) -> &BoardId { &self.board }
+
+    /// Gets the caller's value for the board.
+    pub fn value(&self) -> &T { &self.value }
+}
+
+/// A list of known boards, ranked by their [similarity](BoardId::similarity) to a query, e.g. for
+/// "did you mean" suggestions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoardMatcher<T> {
+    /// The entries, in insertion order.
+    entries: Vec<MatcherEntry<T>>,
+}
+
+impl<T> Default for BoardMatcher<T> {
+    fn default() -> Self { Self { entries: Vec::new() } }
+}
+
+impl<T> BoardMatcher<T> {
+    /// Creates an empty [`BoardMatcher`].
+    pub fn new() -> Self { Self::default() }
+
+    /// Adds a known board, along with a value for it.
+    pub fn push(&mut self, board: BoardId, value: T) {
+        let tags = board.tags();
+        self.entries.push(MatcherEntry { board, value, tags });
+    }
+
+    /// Gets the entries, in insertion order.
+    pub fn entries(&self) -> &[MatcherEntry<T>] { &self.entries }
+
+    /// Gets up to `limit` entries most similar to the query, most similar first, with their
+    /// [similarity](BoardId::similarity) scores.
+    ///
+    /// The scores range from 0 to 1 regardless of the boards' lengths, so a threshold means the
+    /// same for any query. Entries sharing no tag with the query are left out, and entries with
+    /// equal scores are in insertion order.
+    pub fn rank(&self, query: &BoardId, limit: usize) -> Vec<(f32, &MatcherEntry<T>)> {
+        let tags = query.tags();
+        let mut ranked = self.entries.iter()
+            .map(|entry| (jaccard(&tags, &entry.tags), entry))
+            .filter(|&(score, _)| score > 0.0)
+            .collect::<Vec<_>>();
+        // stable, so ties keep the insertion order
+        ranked.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+        ranked.truncate(limit);
+        ranked
+    }
+}
+
+impl<T> FromIterator<(BoardId, T)> for BoardMatcher<T> {
+    fn from_iter<I: IntoIterator<Item = (BoardId, T)>>(iter: I) -> Self {
+        let mut matcher = Self::new();
+        for (board, value) in iter { matcher.push(board, value) }
+        matcher
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    fn board(vendor: &str, name: &str) -> BoardId { BoardId::from_parts(Some(vendor.as_bytes()), Some(name.as_bytes()), None).unwrap() }
+
+    fn matcher() -> BoardMatcher<&'static str> {
+        [
+            (board("ASUSTeK COMPUTER INC.", "PRIME B550-PLUS"), "prime-plus"),
+            (board("ASUSTeK COMPUTER INC.", "PRIME B550M-A"  ), "prime-m"),
+            (board("ASUSTeK COMPUTER INC.", "PRIME B550-PLUS"), "prime-plus-duplicate"),
+            (board("Dell Inc."            , "0UW306"         ), "optiplex"),
+            (board("Gigabyte Technology"  , "B550 AORUS ELITE"), "aorus"),
+        ].into_iter().collect()
+    }
+
+    fn values<'a>(ranked: &[(f32, &'a MatcherEntry<&'static str>)]) -> Vec<&'a str> {
+        ranked.iter().map(|(_, entry)| *entry.value()).collect()
+    }
+
+    #[test]
+    fn ranked() {
+        let matcher = matcher();
+        let ranked = matcher.rank(&BoardId::from_parts(None, Some(b"prime b550 plus"), None).unwrap(), 10);
+        // "b550m" isn't "b550", so the MicroATX PRIME shares fewer tags than the AORUS
+        assert_eq!(values(&ranked), ["prime-plus", "prime-plus-duplicate", "aorus", "prime-m"]);
+        assert!(ranked.windows(2).all(|w| w[0].0 >= w[1].0), "not sorted");
+        assert!(ranked.iter().all(|&(score, _)| 0.0 < score && score <= 1.0));
+        assert_eq!(ranked[0].0, ranked[1].0);
+    }
+
+    #[test]
+    fn limit() {
+        let matcher = matcher();
+        let query = board("ASUSTeK COMPUTER INC.", "PRIME B550-PLUS");
+        let ranked = matcher.rank(&query, 1);
+        assert_eq!(values(&ranked), ["prime-plus"], "ties out of insertion order");
+        assert_eq!(ranked[0].0, 1.0);
+        assert!(matcher.rank(&query, 0).is_empty());
+    }
+
+    #[test]
+    fn no_match() {
+        assert!(matcher().rank(&BoardId::from_parts(None, Some(b"ThinkPad"), None).unwrap(), 10).is_empty());
+        assert!(BoardMatcher::<()>::new().rank(&board("ASUS", "PRIME"), 10).is_empty());
+    }
+}
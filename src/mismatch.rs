@@ -0,0 +1,83 @@
+//! Checking the detected [`BoardId`] against expected parts.
+
+use std::{fmt::{self, Display}, io, path::Path};
+
+use crate::{BoardId, Part};
+
+/// A part of the detected [`BoardId`] that differs from its expectation.
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// The part that differs.
+    pub part: Part,
+    /// The expected value.
+    pub expected: String,
+    /// The detected value, or `None` if undetected.
+    pub actual: Option<Vec<u8>>,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected board {} \"{}\", ", self.part, self.expected.escape_default())?;
+        match &self.actual {
+            Some(actual) => write!(f, "detected \"{}\"", actual.escape_ascii()),
+            None => write!(f, "it's undetected"),
+        }
+    }
+}
+
+impl std::error::Error for Mismatch {}
+
+impl BoardId {
+    /// Checks the [`BoardId`]'s parts against the expected ones, skipping the `None` expectations.
+    ///
+    /// Fails with the first (in vendor, name, version order) part that differs.
+    pub fn check(&self, vendor: Option<&str>, name: Option<&str>, version: Option<&str>) -> Result<(), Mismatch> {
+        for (part, expected) in Part::ALL.into_iter().zip([vendor, name, version]) {
+            let Some(expected) = expected else { continue };
+            let actual = self.part(part);
+            if actual != Some(expected.as_bytes()) {
+                return Err(Mismatch { part, expected: expected.to_owned(), actual: actual.map(<[u8]>::to_vec) })
+            }
+        }
+        Ok(())
+    }
+
+    /// Detects the [`BoardId`] and [checks](Self::check) it against the expected parts.
+    pub fn assert_matches(vendor: Option<&str>, name: Option<&str>, version: Option<&str>) -> io::Result<Result<(), Mismatch>> {
+        Self::assert_matches_at("/", vendor, name, version)
+    }
+
+    /// Detects the [`BoardId`] of the system whose filesystem is rooted at the given path and
+    /// [checks](Self::check) it against the expected parts.
+    pub fn assert_matches_at(root: impl AsRef<Path>, vendor: Option<&str>, name: Option<&str>, version: Option<&str>) -> io::Result<Result<(), Mismatch>> {
+        Ok(Self::detect_at(root)?.check(vendor, name, version))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    #[test]
+    fn matches() {
+        let root = TempDir::with_board("mismatch-matches");
+        assert_eq!(BoardId::assert_matches_at(root.path(), Some("VENDOR"), Some("NAME"), None).unwrap(), Ok(()));
+        assert_eq!(BoardId::assert_matches_at(root.path(), None, None, None).unwrap(), Ok(()));
+    }
+
+    #[test]
+    fn name_mismatch() {
+        let root = TempDir::with_board("mismatch-name");
+        let mismatch = BoardId::assert_matches_at(root.path(), Some("VENDOR"), Some("OTHER"), None).unwrap().unwrap_err();
+        assert_eq!(mismatch, Mismatch { part: Part::Name, expected: "OTHER".to_owned(), actual: Some(b"NAME".to_vec()) });
+        assert_eq!(mismatch.to_string(), "expected board name \"OTHER\", detected \"NAME\"");
+    }
+
+    #[test]
+    fn undetected_mismatch() {
+        let root = TempDir::with_board("mismatch-undetected");
+        let mismatch = BoardId::assert_matches_at(root.path(), None, None, Some("1.0")).unwrap().unwrap_err();
+        assert_eq!(mismatch, Mismatch { part: Part::Version, expected: "1.0".to_owned(), actual: None });
+    }
+}
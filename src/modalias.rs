@@ -0,0 +1,122 @@
+//! Parsing the DMI `modalias`, which combines most DMI identity fields in one attribute.
+
+use crate::{BoardId, CapacityError};
+
+/// The DMI identity fields of a `modalias`.
+///
+/// The kernel strips spaces and colons out of the values when it builds the `modalias`, so e.g. a
+/// board name of "PRIME Z790-P" appears as "PRIMEZ790-P".
+#[derive(Debug, Default, Hash, Clone, Copy, PartialEq, Eq)]
+pub struct ModaliasFields<'a> {
+    /// The BIOS vendor (`bvn`).
+    pub bios_vendor: Option<&'a [u8]>,
+    /// The BIOS version (`bvr`).
+    pub bios_version: Option<&'a [u8]>,
+    /// The BIOS date (`bd`).
+    pub bios_date: Option<&'a [u8]>,
+    /// The BIOS release (`br`).
+    pub bios_release: Option<&'a [u8]>,
+    /// The embedded controller firmware release (`efr`).
+    pub ec_firmware_release: Option<&'a [u8]>,
+    /// The system vendor (`svn`).
+    pub system_vendor: Option<&'a [u8]>,
+    /// The product name (`pn`).
+    pub product_name: Option<&'a [u8]>,
+    /// The product version (`pvr`).
+    pub product_version: Option<&'a [u8]>,
+    /// The product family (`pfa`).
+    pub product_family: Option<&'a [u8]>,
+    /// The product SKU (`sku`).
+    pub product_sku: Option<&'a [u8]>,
+    /// The board vendor (`rvn`).
+    pub board_vendor: Option<&'a [u8]>,
+    /// The board name (`rn`).
+    pub board_name: Option<&'a [u8]>,
+    /// The board version (`rvr`).
+    pub board_version: Option<&'a [u8]>,
+    /// The chassis vendor (`cvn`).
+    pub chassis_vendor: Option<&'a [u8]>,
+    /// The chassis type (`ct`).
+    pub chassis_type: Option<&'a [u8]>,
+    /// The chassis version (`cvr`).
+    pub chassis_version: Option<&'a [u8]>,
+}
+
+impl ModaliasFields<'_> {
+    /// Attempts to make a [`BoardId`] from the board fields.
+    pub fn board_id(&self) -> Result<BoardId, CapacityError> {
+        BoardId::from_parts(self.board_vendor, self.board_name, self.board_version)
+    }
+}
+
+/// Parses a DMI `modalias`, e.g.
+/// `dmi:bvnAmericanMegatrendsInc.:bvr1.0:svnASUS:pnSystemProductName:rvnASUSTeKCOMPUTERINC.:rnPRIMEZ790-P:rvrRev1.xx:`.
+///
+/// The leading `dmi:` and a trailing newline are optional, and unknown segments are ignored.
+/// Empty values are treated as absent.
+pub fn parse_modalias(bytes: &[u8]) -> ModaliasFields<'_> {
+    let bytes = bytes.strip_suffix(b"\n").unwrap_or(bytes);
+    let bytes = bytes.strip_prefix(b"dmi:").unwrap_or(bytes);
+    let mut fields = ModaliasFields::default();
+    for segment in bytes.split(|&b| b == b':') {
+        // the three-letter keys go first, though no two-letter key is a prefix of one anyway
+        let (field, value) = match segment {
+            [b'b', b'v', b'n', value @ ..] => (&mut fields.bios_vendor        , value),
+            [b'b', b'v', b'r', value @ ..] => (&mut fields.bios_version       , value),
+            [b'e', b'f', b'r', value @ ..] => (&mut fields.ec_firmware_release, value),
+            [b's', b'v', b'n', value @ ..] => (&mut fields.system_vendor      , value),
+            [b'p', b'v', b'r', value @ ..] => (&mut fields.product_version    , value),
+            [b'p', b'f', b'a', value @ ..] => (&mut fields.product_family     , value),
+            [b's', b'k', b'u', value @ ..] => (&mut fields.product_sku        , value),
+            [b'r', b'v', b'n', value @ ..] => (&mut fields.board_vendor       , value),
+            [b'r', b'v', b'r', value @ ..] => (&mut fields.board_version      , value),
+            [b'c', b'v', b'n', value @ ..] => (&mut fields.chassis_vendor     , value),
+            [b'c', b'v', b'r', value @ ..] => (&mut fields.chassis_version    , value),
+            [b'b', b'd',       value @ ..] => (&mut fields.bios_date          , value),
+            [b'b', b'r',       value @ ..] => (&mut fields.bios_release       , value),
+            [b'p', b'n',       value @ ..] => (&mut fields.product_name       , value),
+            [b'r', b'n',       value @ ..] => (&mut fields.board_name         , value),
+            [b'c', b't',       value @ ..] => (&mut fields.chassis_type       , value),
+            _ => continue,
+        };
+        *field = (!value.is_empty()).then_some(value);
+    }
+    fields
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    /// The `modalias` of an ASUS desktop.
+    const MODALIAS: &[u8] = b"dmi:bvnAmericanMegatrendsInc.:bvr1662:bd06/25/2024:br16.62:svnASUS:pnSystemProductName:pvrSystemVersion:rvnASUSTeKCOMPUTERINC.:rnPRIMEZ790-PWIFI:rvrRevx.0x:cvnDefaultstring:ct3:cvrDefaultstring:skuSKU:\n";
+
+    #[test]
+    fn fields() {
+        let fields = parse_modalias(MODALIAS);
+        assert_eq!(fields, ModaliasFields {
+            bios_vendor    : Some(b"AmericanMegatrendsInc."),
+            bios_version   : Some(b"1662"),
+            bios_date      : Some(b"06/25/2024"),
+            bios_release   : Some(b"16.62"),
+            system_vendor  : Some(b"ASUS"),
+            product_name   : Some(b"SystemProductName"),
+            product_version: Some(b"SystemVersion"),
+            product_sku    : Some(b"SKU"),
+            board_vendor   : Some(b"ASUSTeKCOMPUTERINC."),
+            board_name     : Some(b"PRIMEZ790-PWIFI"),
+            board_version  : Some(b"Revx.0x"),
+            chassis_vendor : Some(b"Defaultstring"),
+            chassis_type   : Some(b"3"),
+            chassis_version: Some(b"Defaultstring"),
+            ..ModaliasFields::default()
+        });
+        assert_eq!(fields.board_id().unwrap().to_string(), "ASUSTeKCOMPUTERINC. PRIMEZ790-PWIFI Revx.0x");
+    }
+
+    #[test]
+    fn unknown_and_empty() {
+        let fields = parse_modalias(b"xyzzy:rn:rvnVENDOR:pfaFAMILY");
+        assert_eq!(fields, ModaliasFields { board_vendor: Some(b"VENDOR"), product_family: Some(b"FAMILY"), ..ModaliasFields::default() });
+    }
+}
@@ -0,0 +1,287 @@
+//! [`DetectOptions`], the knobs of [`BoardId::detect_with_options`].
+
+use std::{error::Error, fmt::{self, Display}, io, path::{Path, PathBuf}};
+
+use crate::{dmi, open_existing_file, sysfs, BoardId, DmiField, FsOpener, NoFollowOpener, Opener, Part, ScanSession, Source};
+
+/// The knobs of [`BoardId::detect_with_options`].
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub struct DetectOptions {
+    /// Whether to discover where sysfs is mounted (from `/proc/self/mounts`) when the DMI directory
+    /// isn't under `/sys`, and retry under the discovered mountpoint. On by default.
+    pub discover_sysfs: bool,
+    /// Whether to follow symlinks to the files read. Otherwise, a symlinked file fails detection
+    /// as per [`NoFollowOpener`]. On by default.
+    pub follow_symlinks: bool,
+    /// Whether to take the name from the `product_name` DMI attribute when `board_name` is missing
+    /// or a [placeholder](crate::is_placeholder) (common on laptops). Off by default.
+    pub fallback_name_to_product: bool,
+    /// Whether to take the vendor from the `sys_vendor` DMI attribute when `board_vendor` is
+    /// missing or a placeholder. Off by default.
+    pub fallback_vendor_to_sys_vendor: bool,
+    /// Whether each part, in [`Part::ALL`] order, is required. Detection fails with a
+    /// [`MissingRequired`] error for the first required part that's undetected or a
+    /// [placeholder](crate::is_placeholder) after the fallbacks. None by default. See
+    /// [`Self::require`].
+    pub required: [bool; 3],
+}
+
+impl Default for DetectOptions {
+    fn default() -> Self {
+        Self {
+            discover_sysfs: true, follow_symlinks: true,
+            fallback_name_to_product: false, fallback_vendor_to_sys_vendor: false,
+            required: [false; 3],
+        }
+    }
+}
+
+impl DetectOptions {
+    /// Requires the parts, in addition to those already [required](Self::required).
+    pub fn require(mut self, parts: &[Part]) -> Self {
+        for &part in parts { self.required[part as usize] = true }
+        self
+    }
+}
+
+/// The error for a part [required](DetectOptions::required) but not detected.
+///
+/// It's converted to an [`io::ErrorKind::NotFound`] error, from which it can be recovered with
+/// [`io::Error::get_ref`] and a downcast.
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub struct MissingRequired {
+    /// The missing part.
+    pub part: Part,
+}
+
+impl Display for MissingRequired {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the required motherboard {} wasn't detected", self.part)
+    }
+}
+
+impl Error for MissingRequired {}
+
+/// Converts to an [`io::ErrorKind::NotFound`] error.
+impl From<MissingRequired> for io::Error {
+    fn from(e: MissingRequired) -> Self { io::Error::new(io::ErrorKind::NotFound, e) }
+}
+
+/// Where a [`BoardId`] detected by [`BoardId::detect_with_options_provenance`] came from.
+#[derive(Debug, Default, Hash, Clone, Copy, PartialEq, Eq)]
+pub struct Provenance {
+    /// The source the board was detected from (`None` if undetected).
+    pub source: Option<Source>,
+    /// Whether the name fell back to `product_name`.
+    pub name_from_product: bool,
+    /// Whether the vendor fell back to `sys_vendor`.
+    pub vendor_from_sys_vendor: bool,
+}
+
+impl BoardId {
+    /// Attempts to detect the [`BoardId`] with the given options.
+    ///
+    /// See [`Self::detect`] and [`Self::detect_with_options_at`].
+    pub fn detect_with_options(options: &DetectOptions) -> io::Result<Self> { Self::detect_with_options_at("/", options) }
+
+    /// Attempts to detect the [`BoardId`] of the system whose filesystem is rooted at the given
+    /// path, with the given options.
+    ///
+    /// Unless an option says otherwise, it's the same as [`Self::detect_at`]. The mount table of
+    /// [`DetectOptions::discover_sysfs`] is read from under the root, and so is the mountpoint it
+    /// lists. The fallbacks only apply to boards detected from DMI, or undetected.
+    pub fn detect_with_options_at(root: impl AsRef<Path>, options: &DetectOptions) -> io::Result<Self> {
+        Ok(Self::detect_with_options_provenance_at(root, options)?.0)
+    }
+
+    /// Attempts to detect the [`BoardId`] with the given options, along with its [`Provenance`].
+    ///
+    /// See [`Self::detect_with_options_provenance_at`].
+    pub fn detect_with_options_provenance(options: &DetectOptions) -> io::Result<(Self, Provenance)> {
+        Self::detect_with_options_provenance_at("/", options)
+    }
+
+    /// Attempts to detect the [`BoardId`] of the system whose filesystem is rooted at the given
+    /// path with the given options, along with its [`Provenance`].
+    ///
+    /// See [`Self::detect_with_options_at`].
+    pub fn detect_with_options_provenance_at(root: impl AsRef<Path>, options: &DetectOptions) -> io::Result<(Self, Provenance)> {
+        match options.follow_symlinks {
+            true  => Self::detect_with_options_opener(root.as_ref(), options, &mut FsOpener),
+            false => Self::detect_with_options_opener(root.as_ref(), options, &mut NoFollowOpener),
+        }
+    }
+
+    /// The logic of [`Self::detect_with_options_provenance_at`], opening files with the given
+    /// opener.
+    fn detect_with_options_opener(root: &Path, options: &DetectOptions, opener: &mut impl Opener) -> io::Result<(Self, Provenance)> {
+        let mut dir = root.join(dmi::DIR);
+        let mut detected = None;
+        if options.discover_sysfs && !dir.is_dir() {
+            if let Some(discovered) = discover_dmi_dir(root)? {
+                detected = Some(Self::from_streams(
+                    opener.open(&discovered.join(DmiField::BoardVendor .attribute()))?,
+                    opener.open(&discovered.join(DmiField::BoardName   .attribute()))?,
+                    opener.open(&discovered.join(DmiField::BoardVersion.attribute()))?,
+                )?).filter(|board| !board.is_undetected());
+                dir = discovered;
+            }
+        }
+        let (mut board, source) = match detected {
+            Some(board) => (board, Some(Source::Dmi)),
+            None => ScanSession::new().detect_with(opener, root, None)?,
+        };
+        let mut provenance = Provenance { source, ..Provenance::default() };
+        if matches!(source, None | Some(Source::Dmi)) { Self::apply_fallbacks(&dir, options, opener, &mut board, &mut provenance)? }
+        if let Some(part) = Part::ALL.into_iter().find(|&part| options.required[part as usize] && board.part_canonical(part).is_none()) {
+            return Err(MissingRequired { part }.into())
+        }
+        Ok((board, provenance))
+    }
+
+    /// Applies the fallbacks of the options to the board detected from DMI (or undetected), from
+    /// the DMI directory.
+    fn apply_fallbacks(dir: &Path, options: &DetectOptions, opener: &mut impl Opener, board: &mut Self, provenance: &mut Provenance) -> io::Result<()> {
+        for (enabled, part, field, fired) in [
+            (options.fallback_vendor_to_sys_vendor, Part::Vendor, DmiField::SysVendor  , &mut provenance.vendor_from_sys_vendor),
+            (options.fallback_name_to_product     , Part::Name  , DmiField::ProductName, &mut provenance.name_from_product),
+        ] {
+            if !enabled || board.part_canonical(part).is_some() { continue }
+            let Some(file) = opener.open(&dir.join(field.attribute()))? else { continue };
+            // read as a vendor, for the same trimming and capacity checks
+            let value = Self::from_streams(Some(file), None::<&[u8]>, None::<&[u8]>)?;
+            let Some(value) = value.part_canonical(Part::Vendor) else { continue };
+            let mut parts = Part::ALL.map(|part| board.part(part));
+            parts[part as usize] = Some(value);
+            *board = Self::from_parts(parts[0], parts[1], parts[2])?;
+            *fired = true;
+        }
+        if provenance.source.is_none() && !board.is_undetected() { provenance.source = Some(Source::Dmi) }
+        Ok(())
+    }
+}
+
+/// Discovers the DMI directory under the sysfs mountpoint the mount table (under the root) lists,
+/// returning `Ok(None)` if there's none other than `/sys`.
+fn discover_dmi_dir(root: &Path) -> io::Result<Option<PathBuf>> {
+    let Some(mounts) = open_existing_file(root.join(sysfs::MOUNTS))? else { return Ok(None) };
+    let Some(mountpoint) = sysfs::find_mountpoint(mounts)? else { return Ok(None) };
+    if mountpoint == Path::new("/sys") { return Ok(None) }
+    Ok(Some(sysfs::under_root(root, &mountpoint).join("class/dmi/id")))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    #[test]
+    fn discovers_sysfs() {
+        let root = TempDir::new("options-discovers-sysfs");
+        root.write("proc/self/mounts", "proc /proc proc rw 0 0\nsysfs /run/sysfs sysfs rw 0 0\n");
+        root.write("run/sysfs/class/dmi/id/board_vendor", "VENDOR\n");
+        root.write("run/sysfs/class/dmi/id/board_name", "NAME\n");
+        let board = BoardId::detect_with_options_at(root.path(), &DetectOptions::default()).unwrap();
+        assert_eq!(board.to_string(), "VENDOR NAME");
+        let disabled = DetectOptions { discover_sysfs: false, ..DetectOptions::default() };
+        assert!(BoardId::detect_with_options_at(root.path(), &disabled).unwrap().is_undetected());
+    }
+
+    #[test]
+    fn prefers_sys() {
+        let root = TempDir::new("options-prefers-sys");
+        root.write("proc/self/mounts", "sysfs /run/sysfs sysfs rw 0 0\n");
+        root.write("run/sysfs/class/dmi/id/board_name", "OTHER\n");
+        root.write("sys/class/dmi/id/board_name", "NAME\n");
+        assert_eq!(BoardId::detect_with_options_at(root.path(), &DetectOptions::default()).unwrap().name(), Some(&b"NAME"[..]));
+    }
+
+    #[test]
+    fn falls_back() {
+        // no mount table, or sysfs only at /sys: the device tree is still tried
+        let root = TempDir::new("options-falls-back");
+        root.write("proc/device-tree/model", "MODEL\0");
+        assert_eq!(BoardId::detect_with_options_at(root.path(), &DetectOptions::default()).unwrap().name(), Some(&b"MODEL"[..]));
+        root.write("proc/self/mounts", "sysfs /sys sysfs rw 0 0\n");
+        assert_eq!(BoardId::detect_with_options_at(root.path(), &DetectOptions::default()).unwrap().name(), Some(&b"MODEL"[..]));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks() {
+        let root = TempDir::new("options-symlinks");
+        let target = root.write("elsewhere/name", "NAME\n");
+        root.write("sys/class/dmi/id/board_vendor", "VENDOR\n");
+        std::os::unix::fs::symlink(target, root.path().join("sys/class/dmi/id/board_name")).unwrap();
+        let board = BoardId::detect_with_options_at(root.path(), &DetectOptions::default()).unwrap();
+        assert_eq!(board.to_string(), "VENDOR NAME");
+        let no_follow = DetectOptions { follow_symlinks: false, ..DetectOptions::default() };
+        let e = BoardId::detect_with_options_at(root.path(), &no_follow).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::PermissionDenied);
+    }
+
+    fn fallback_options() -> DetectOptions {
+        DetectOptions { fallback_name_to_product: true, fallback_vendor_to_sys_vendor: true, ..DetectOptions::default() }
+    }
+
+    #[test]
+    fn name_fallback_on_placeholder() {
+        let root = TempDir::new("options-fallback-placeholder");
+        root.write("sys/class/dmi/id/board_vendor", "LENOVO\n");
+        root.write("sys/class/dmi/id/board_name", "Default string\n");
+        root.write("sys/class/dmi/id/board_version", "1.0\n");
+        root.write("sys/class/dmi/id/product_name", "ThinkPad T14 Gen 2i\n");
+        let (board, provenance) = BoardId::detect_with_options_provenance_at(root.path(), &fallback_options()).unwrap();
+        assert_eq!(board.as_tuple(), (Some(&b"LENOVO"[..]), Some(&b"ThinkPad T14 Gen 2i"[..]), Some(&b"1.0"[..])));
+        assert_eq!(provenance, Provenance { source: Some(Source::Dmi), name_from_product: true, vendor_from_sys_vendor: false });
+        let (board, provenance) = BoardId::detect_with_options_provenance_at(root.path(), &DetectOptions::default()).unwrap();
+        assert_eq!(board.name(), Some(&b"Default string"[..]));
+        assert_eq!(provenance, Provenance { source: Some(Source::Dmi), ..Provenance::default() });
+    }
+
+    #[test]
+    fn fallback_on_absence() {
+        let root = TempDir::new("options-fallback-absence");
+        root.write("sys/class/dmi/id/sys_vendor", "QEMU\n");
+        root.write("sys/class/dmi/id/product_name", "Standard PC (Q35 + ICH9, 2009)\n");
+        let (board, provenance) = BoardId::detect_with_options_provenance_at(root.path(), &fallback_options()).unwrap();
+        assert_eq!(board.to_string(), "QEMU Standard PC (Q35 + ICH9, 2009)");
+        assert_eq!(provenance, Provenance { source: Some(Source::Dmi), name_from_product: true, vendor_from_sys_vendor: true });
+        root.write("sys/class/dmi/id/product_name", "To be filled by O.E.M.\n");
+        let (board, provenance) = BoardId::detect_with_options_provenance_at(root.path(), &fallback_options()).unwrap();
+        assert_eq!(board.name(), None, "placeholder product name used");
+        assert!(!provenance.name_from_product);
+    }
+
+    #[test]
+    fn no_fallback_for_device_tree() {
+        let root = TempDir::new("options-fallback-device-tree");
+        root.write("proc/device-tree/model", "MODEL\0");
+        root.write("sys/class/dmi/id/product_name", "PRODUCT\n");
+        let (board, provenance) = BoardId::detect_with_options_provenance_at(root.path(), &fallback_options()).unwrap();
+        assert_eq!(board.name(), Some(&b"MODEL"[..]));
+        assert_eq!(provenance, Provenance { source: Some(Source::ProcDeviceTree), ..Provenance::default() });
+    }
+
+    fn missing(root: &Path, options: &DetectOptions) -> Option<Part> {
+        let e = BoardId::detect_with_options_at(root, options).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::NotFound);
+        e.get_ref()?.downcast_ref::<MissingRequired>().map(|e| e.part)
+    }
+
+    #[test]
+    fn required() {
+        let root = TempDir::new("options-required");
+        root.write("sys/class/dmi/id/board_vendor", "LENOVO\n");
+        root.write("sys/class/dmi/id/board_name", "Default string\n");
+        root.write("sys/class/dmi/id/product_name", "ThinkPad T14 Gen 2i\n");
+        let options = DetectOptions::default().require(&[Part::Vendor, Part::Name]);
+        assert_eq!(options.required, [true, true, false]);
+        assert_eq!(missing(root.path(), &options), Some(Part::Name));
+        assert_eq!(missing(root.path(), &options.require(&[Part::Version])), Some(Part::Name), "reported a later part");
+        let fallback = DetectOptions { fallback_name_to_product: true, ..options };
+        assert_eq!(BoardId::detect_with_options_at(root.path(), &fallback).unwrap().name(), Some(&b"ThinkPad T14 Gen 2i"[..]));
+        assert_eq!(missing(root.path(), &fallback.require(&[Part::Version])), Some(Part::Version));
+        assert_eq!(BoardId::detect_with_options_at(root.path(), &DetectOptions::default()).unwrap().version(), None, "unrequired part failed");
+    }
+}
@@ -0,0 +1,157 @@
+//! [`PinnedBoardId`], for checking that the [`BoardId`] hasn't changed mid-session.
+
+use std::{fmt::{self, Display}, io, path::{Path, PathBuf}, thread::{self, JoinHandle}, time::Duration};
+
+use crate::{BoardId, Part};
+
+/// A part of a [`PinnedBoardId`] that changed.
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub struct PartChange {
+    /// The part that changed.
+    pub part: Part,
+    /// The pinned value, or `None` if it was undetected.
+    pub old: Option<Vec<u8>>,
+    /// The re-detected value, or `None` if it's undetected.
+    pub new: Option<Vec<u8>>,
+}
+
+impl PartChange {
+    /// Gets the parts that differ between the boards, in vendor, name, version order. The values
+    /// are compared in constant time.
+    pub(crate) fn between(old: &BoardId, new: &BoardId) -> Vec<Self> {
+        Part::ALL.into_iter()
+            .filter(|&part| !ct_eq(old.part(part), new.part(part)))
+            .map(|part| Self { part, old: old.part(part).map(<[u8]>::to_vec), new: new.part(part).map(<[u8]>::to_vec) })
+            .collect()
+    }
+}
+
+/// The parts of a [`PinnedBoardId`] that changed, in vendor, name, version order.
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub struct PinMismatch(pub Vec<PartChange>);
+
+impl Display for PinMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "pinned board changed")?;
+        for (i, change) in self.0.iter().enumerate() {
+            write!(f, "{} {} {} -> {}", if i == 0 { ":" } else { "," }, change.part, PartValue(change.old.as_deref()), PartValue(change.new.as_deref()))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for PinMismatch {}
+
+/// Displays a part's value in mismatch reports: quoted and escaped, or `undetected`.
+pub(crate) struct PartValue<'a>(pub Option<&'a [u8]>);
+
+impl Display for PartValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(value) => write!(f, "\"{}\"", value.escape_ascii()),
+            None        => write!(f, "undetected"),
+        }
+    }
+}
+
+/// Compares the values in constant time (for their lengths), so the comparison doesn't leak how
+/// much of them matches.
+pub(crate) fn ct_eq(a: Option<&[u8]>, b: Option<&[u8]>) -> bool {
+    let (a_detected, b_detected) = (a.is_some(), b.is_some());
+    let (a, b) = (a.unwrap_or_default(), b.unwrap_or_default());
+    let diff = (0..a.len().max(b.len()))
+        .fold((a.len() != b.len()) as u8, |diff, i| diff | (a.get(i).unwrap_or(&0) ^ b.get(i).unwrap_or(&0)));
+    diff == 0 && a_detected == b_detected
+}
+
+/// A [`BoardId`] detected at some point (e.g. startup), to [verify](Self::verify) later that the
+/// identity still matches, e.g. to catch a sysfs bind-mounted over or a migrated process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinnedBoardId {
+    /// The pinned board.
+    board: BoardId,
+    /// The root of the filesystem the board was detected from.
+    root: PathBuf,
+}
+
+impl PinnedBoardId {
+    /// Detects the [`BoardId`] and pins it.
+    pub fn pin() -> io::Result<Self> { Self::pin_at("/") }
+
+    /// Detects the [`BoardId`] of the system whose filesystem is rooted at the given path and pins
+    /// it.
+    pub fn pin_at(root: impl AsRef<Path>) -> io::Result<Self> {
+        let root = root.as_ref().to_owned();
+        Ok(Self { board: BoardId::detect_at(&root)?, root })
+    }
+
+    /// Gets the pinned [`BoardId`].
+    pub fn board(&self) -> &BoardId { &self.board }
+
+    /// Re-detects the [`BoardId`] and checks it against the pinned one.
+    ///
+    /// Fails with every part that changed, old and new values. The values are compared in constant
+    /// time.
+    pub fn verify(&self) -> io::Result<Result<(), PinMismatch>> {
+        let board = BoardId::detect_at(&self.root)?;
+        let changes = PartChange::between(&self.board, &board);
+        Ok(if changes.is_empty() { Ok(()) } else { Err(PinMismatch(changes)) })
+    }
+
+    /// Spawns a thread that [verifies](Self::verify) the pinned board every interval, passing the
+    /// result to the callback, until the callback returns `false`.
+    pub fn verify_every(self, interval: Duration, mut callback: impl FnMut(io::Result<Result<(), PinMismatch>>) -> bool + Send + 'static) -> JoinHandle<()> {
+        thread::spawn(move || loop {
+            thread::sleep(interval);
+            if !callback(self.verify()) { break }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::mpsc;
+
+    use super::*;
+    use crate::fixture::TempDir;
+
+    #[test]
+    fn constant_time_eq() {
+        assert!(ct_eq(Some(b"NAME"), Some(b"NAME")));
+        assert!(ct_eq(None, None));
+        assert!(!ct_eq(Some(b"NAME"), Some(b"NAMF")));
+        assert!(!ct_eq(Some(b"NAME"), Some(b"NAME\0")));
+        assert!(!ct_eq(Some(b""), None));
+    }
+
+    #[test]
+    fn verify() {
+        let root = TempDir::with_board("pin-verify");
+        let pinned = PinnedBoardId::pin_at(root.path()).unwrap();
+        assert_eq!(pinned.verify().unwrap(), Ok(()));
+        root.write("sys/class/dmi/id/board_name", "OTHER\n");
+        root.write("sys/class/dmi/id/board_version", "1.0\n");
+        let mismatch = pinned.verify().unwrap().unwrap_err();
+        assert_eq!(mismatch, PinMismatch(vec![
+            PartChange { part: Part::Name   , old: Some(b"NAME".to_vec()), new: Some(b"OTHER".to_vec()) },
+            PartChange { part: Part::Version, old: None                  , new: Some(b"1.0".to_vec())   },
+        ]));
+        assert_eq!(mismatch.to_string(), "pinned board changed: name \"NAME\" -> \"OTHER\", version undetected -> \"1.0\"");
+    }
+
+    #[test]
+    fn verify_every() {
+        let root = TempDir::with_board("pin-verify-every");
+        let pinned = PinnedBoardId::pin_at(root.path()).unwrap();
+        let (tx, rx) = mpsc::channel();
+        let checker = pinned.verify_every(Duration::from_millis(1), move |result| {
+            let mismatched = result.unwrap().is_err();
+            tx.send(mismatched).unwrap();
+            !mismatched
+        });
+        assert!(!rx.recv().unwrap());
+        root.write("sys/class/dmi/id/board_vendor", "OTHER\n");
+        while !rx.recv().unwrap() {}
+        checker.join().unwrap();
+    }
+}
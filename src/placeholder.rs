@@ -0,0 +1,141 @@
+//! Recognizing the placeholder values firmware vendors leave in unset DMI fields, and junk serials.
+
+use alloc::string::String;
+
+use crate::{BoardId, Part};
+
+/// Known placeholder values, compared case-insensitively.
+const PLACEHOLDERS: &[&[u8]] = &[
+    b"To be filled by O.E.M.",
+    b"To be filled by OEM",
+    b"System manufacturer",
+    b"System Product Name",
+    b"System Version",
+    b"Base Board Manufacturer",
+    b"Base Board Product Name",
+    b"Base Board Version",
+    b"Type2 - Board Vendor Name1",
+    b"Type2 - Board Product Name1",
+    b"Type2 - Board Version",
+    b"Default string",
+    b"Not Applicable",
+    b"Not Specified",
+    b"Not Available",
+    b"O.E.M.",
+    b"OEM",
+    b"None",
+    b"Unknown",
+    b"N/A",
+    b"x.x",
+    b"",
+];
+
+/// Checks whether the value is a known placeholder (e.g. "To be filled by O.E.M.") rather than
+/// real data.
+///
+/// Surrounding whitespace and case are ignored.
+pub fn is_placeholder(value: &[u8]) -> bool {
+    let value = value.trim_ascii();
+    PLACEHOLDERS.iter().any(|placeholder| placeholder.eq_ignore_ascii_case(value))
+}
+
+/// The minimum length of a [serial](looks_like_serial).
+const SERIAL_MIN_LEN: usize = 6;
+
+/// Checks whether the value looks like a real serial number (e.g. "CN0ABC123"), rather than junk,
+/// e.g. to validate the serial DMI attributes.
+///
+/// It does if, with surrounding whitespace ignored, it's at least 6 characters of uppercase
+/// letters, digits, `-`, `.` and `/`, with at least one digit, and isn't a
+/// [placeholder](is_placeholder), a repetition of one character (e.g. "000000"), or a run of
+/// consecutive digits (e.g. "123456789").
+pub fn looks_like_serial(value: &[u8]) -> bool {
+    let value = value.trim_ascii();
+    value.len() >= SERIAL_MIN_LEN
+        && value.iter().all(|&b| b.is_ascii_uppercase() || b.is_ascii_digit() || b"-./".contains(&b))
+        && value.iter().any(u8::is_ascii_digit)
+        && value.iter().any(|&b| b != value[0])
+        && !value.windows(2).all(|w| w[1] == w[0] + 1)
+        && !is_placeholder(value)
+}
+
+impl BoardId {
+    /// Gets the part, or `None` if it's undetected or a [placeholder](is_placeholder).
+    pub fn part_canonical(&self, part: Part) -> Option<&[u8]> {
+        self.part(part).filter(|value| !is_placeholder(value))
+    }
+
+    /// Checks whether the [`BoardId`]s' parts are equal once [placeholders](is_placeholder) are
+    /// treated as undetected.
+    ///
+    /// E.g. two whitebox boards whose vendor is "System manufacturer" are equal to each other and to
+    /// a board with no vendor detected, when the rest of their parts are.
+    pub fn eq_canonical(&self, other: &BoardId) -> bool {
+        Part::ALL.into_iter().all(|part| self.part_canonical(part) == other.part_canonical(part))
+    }
+
+    /// Gets a normalized key for deduplicating [`BoardId`]s, e.g. as a `HashSet` entry.
+    ///
+    /// Each part is [canonical](Self::part_canonical), trimmed and lowercased, and the parts are
+    /// NUL-separated, so variants that differ only in case, surrounding whitespace or placeholders
+    /// share a key.
+    pub fn dedup_key(&self) -> String {
+        let mut key = String::new();
+        for (i, part) in Part::ALL.into_iter().enumerate() {
+            if i > 0 { key.push('\0') }
+            let Some(value) = self.part_canonical(part) else { continue };
+            key.push_str(&String::from_utf8_lossy(value).trim().to_lowercase());
+        }
+        key
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    fn board(vendor: Option<&[u8]>, name: Option<&[u8]>) -> BoardId { BoardId::from_parts(vendor, name, None).unwrap() }
+
+    #[test]
+    fn placeholders() {
+        assert!( is_placeholder(b"To Be Filled By O.E.M."));
+        assert!( is_placeholder(b"  Default string "));
+        assert!(!is_placeholder(b"ASUSTeK COMPUTER INC."));
+    }
+
+    #[test]
+    fn serials() {
+        for serial in [&b"CN0ABC123"[..], b"201075725201234", b"..CN697027AB0123.", b"PF2ABCDE "] {
+            assert!(looks_like_serial(serial), "{serial:?}");
+        }
+        for junk in [&b"0"[..], b"To be filled", b"To Be Filled By O.E.M.", b"000000000", b"0123456789", b"ABCDEFGH", b"cn0abc123", b""] {
+            assert!(!looks_like_serial(junk), "{junk:?}");
+        }
+    }
+
+    #[test]
+    fn placeholder_is_absent() {
+        assert!(board(Some(b"System manufacturer"), Some(b"NAME")).eq_canonical(&board(None, Some(b"NAME"))));
+        assert!(board(Some(b"System manufacturer"), None).eq_canonical(&board(Some(b"To be filled by O.E.M."), None)));
+        assert_ne!(board(Some(b"System manufacturer"), None), board(None, None));
+    }
+
+    #[test]
+    fn dedup_key() {
+        let keys = [
+            board(Some(b"ASUSTeK"), Some(b"PRIME B550")),
+            board(Some(b"asustek "), Some(b"  Prime b550")),
+            board(Some(b"ASUSTEK"), Some(b"PRIME B550\t")),
+        ].map(|board| board.dedup_key());
+        assert!(keys.iter().all(|key| *key == keys[0]), "{keys:?}");
+        assert_eq!(board(Some(b"Default string"), Some(b"NAME")).dedup_key(), board(None, Some(b"name")).dedup_key());
+        assert_ne!(board(Some(b"AB"), None).dedup_key(), board(Some(b"A"), Some(b"B")).dedup_key());
+    }
+
+    #[test]
+    fn real_values() {
+        assert!( board(Some(b"VENDOR"), Some(b"NAME")).eq_canonical(&board(Some(b"VENDOR"), Some(b"NAME"))));
+        assert!(!board(Some(b"VENDOR"), Some(b"NAME")).eq_canonical(&board(Some(b"VENDOR"), Some(b"OTHER"))));
+        assert!(!board(Some(b"VENDOR"), None).eq_canonical(&board(None, None)));
+    }
+}
@@ -0,0 +1,180 @@
+//! [`Prober`], for repeated detection that remembers which data sources exist.
+
+use std::{fs::{self, File}, io::{self, Read}, path::{Path, PathBuf}};
+
+use crate::{open_existing_file, scan::Sources, BoardId, ScanSession};
+
+/// Opens the files that detection reads.
+pub trait Opener {
+    /// The type of opened files.
+    type File: Read;
+
+    /// Opens the file at the path, returning `Ok(None)` if it doesn't exist.
+    fn open(&mut self, path: &Path) -> io::Result<Option<Self::File>>;
+
+    /// Checks whether the directory at the path exists.
+    fn dir_exists(&mut self, path: &Path) -> io::Result<bool>;
+}
+
+/// The filesystem's [`Opener`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FsOpener;
+
+impl Opener for FsOpener {
+    type File = File;
+
+    fn open(&mut self, path: &Path) -> io::Result<Option<File>> { open_existing_file(path) }
+
+    fn dir_exists(&mut self, path: &Path) -> io::Result<bool> {
+        match fs::metadata(path) {
+            Ok(metadata) => Ok(metadata.is_dir()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// The filesystem's [`Opener`], refusing to follow symlinks to the files (like `O_NOFOLLOW`), e.g.
+/// for sandboxed readers wary of symlink attacks.
+///
+/// Opening a symlink fails with an [`io::ErrorKind::PermissionDenied`] error. Only the files are
+/// checked, since sysfs itself links its directories (e.g. `/sys/class/dmi/id`). On Unix, a file
+/// swapped for a symlink between the check and the open is caught too.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NoFollowOpener;
+
+impl Opener for NoFollowOpener {
+    type File = File;
+
+    fn open(&mut self, path: &Path) -> io::Result<Option<File>> {
+        let metadata = match fs::symlink_metadata(path) {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let refuse = || io::Error::new(io::ErrorKind::PermissionDenied, format!("refusing to follow the symlink {}", path.display()));
+        if metadata.file_type().is_symlink() { return Err(refuse()) }
+        let Some(file) = open_existing_file(path)? else { return Ok(None) };
+        #[cfg(unix)] {
+            use std::os::unix::fs::MetadataExt;
+            let opened = file.metadata()?;
+            if (opened.dev(), opened.ino()) != (metadata.dev(), metadata.ino()) { return Err(refuse()) }
+        }
+        Ok(Some(file))
+    }
+
+    fn dir_exists(&mut self, path: &Path) -> io::Result<bool> { FsOpener.dir_exists(path) }
+}
+
+/// Repeatedly detects the [`BoardId`] of a system, remembering which data sources (DMI, device
+/// tree) exist across detections so that absent ones aren't probed for again.
+///
+/// The files of the existing sources are still read on every detection, so changed values are
+/// picked up. Sources that appear or disappear after they were probed aren't noticed until the
+/// prober is [invalidated](Self::invalidate).
+#[derive(Debug, Clone)]
+pub struct Prober<O = FsOpener> {
+    /// The root of the system's filesystem.
+    root: PathBuf,
+    /// The opener.
+    opener: O,
+    /// The scratch space.
+    session: ScanSession,
+    /// The known sources.
+    sources: Sources,
+}
+
+impl Prober {
+    /// Creates a prober for the running system.
+    pub fn new() -> Self { Self::at("/") }
+
+    /// Creates a prober for the system whose filesystem is rooted at the given path.
+    pub fn at(root: impl Into<PathBuf>) -> Self { Self::with_opener(root, FsOpener) }
+}
+
+impl Default for Prober {
+    fn default() -> Self { Self::new() }
+}
+
+impl<O: Opener> Prober<O> {
+    /// Creates a prober for the system whose filesystem is rooted at the given path, opening files
+    /// with the given opener.
+    pub fn with_opener(root: impl Into<PathBuf>, opener: O) -> Self {
+        Self { root: root.into(), opener, session: ScanSession::new(), sources: Sources::default() }
+    }
+
+    /// Attempts to detect the [`BoardId`].
+    ///
+    /// See [`BoardId::detect_at`].
+    pub fn detect(&mut self) -> io::Result<BoardId> {
+        Ok(self.session.detect_with(&mut self.opener, &self.root, Some(&mut self.sources))?.0)
+    }
+
+    /// Forgets which sources exist, so the next detection probes for all of them again.
+    pub fn invalidate(&mut self) { self.sources = Sources::default() }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    /// An [`Opener`] counting its calls.
+    #[derive(Default)]
+    struct CountingOpener { calls: usize }
+
+    impl Opener for CountingOpener {
+        type File = File;
+
+        fn open(&mut self, path: &Path) -> io::Result<Option<File>> {
+            self.calls += 1;
+            FsOpener.open(path)
+        }
+
+        fn dir_exists(&mut self, path: &Path) -> io::Result<bool> {
+            self.calls += 1;
+            FsOpener.dir_exists(path)
+        }
+    }
+
+    #[test]
+    fn fewer_calls() {
+        let root = TempDir::new("probe-fewer-calls");
+        root.write("sys/firmware/devicetree/base/model", "MODEL\0");
+        let mut prober = Prober::with_opener(root.path(), CountingOpener::default());
+
+        let first = prober.detect().unwrap();
+        let first_calls = std::mem::take(&mut prober.opener.calls);
+        let second = prober.detect().unwrap();
+        let second_calls = std::mem::take(&mut prober.opener.calls);
+        assert_eq!(first, second);
+        assert_eq!(first, BoardId::detect_at(root.path()).unwrap());
+        assert_eq!(first_calls, 5, "probes DMI and both device tree directories, and reads the present one");
+        assert_eq!(second_calls, 2, "only reads the present device tree directory");
+
+        prober.invalidate();
+        prober.detect().unwrap();
+        assert_eq!(prober.opener.calls, first_calls);
+    }
+
+    #[test]
+    fn rereads_contents() {
+        let root = TempDir::new("probe-rereads");
+        root.write("sys/class/dmi/id/board_name", "NAME\n");
+        let mut prober = Prober::at(root.path());
+        assert_eq!(prober.detect().unwrap().name(), Some("NAME".as_bytes()));
+        root.write("sys/class/dmi/id/board_name", "OTHER\n");
+        assert_eq!(prober.detect().unwrap().name(), Some("OTHER".as_bytes()));
+    }
+
+    #[test]
+    fn invalidate() {
+        let root = TempDir::new("probe-invalidate");
+        let mut prober = Prober::at(root.path());
+        assert_eq!(prober.detect().unwrap().name(), None);
+        root.write("sys/class/dmi/id/board_name", "NAME\n");
+        assert_eq!(prober.detect().unwrap().name(), None, "DMI was remembered as absent");
+        prober.invalidate();
+        assert_eq!(prober.detect().unwrap().name(), Some("NAME".as_bytes()));
+    }
+}
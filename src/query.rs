@@ -0,0 +1,58 @@
+//! Rendering [`BoardId`]s as URL query strings.
+
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::{BoardId, DmiField};
+
+/// Appends the bytes to the string, percent-encoding all but the URL unreserved characters.
+fn percent_encode(out: &mut String, bytes: &[u8]) {
+    for &b in bytes {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => out.push(b as char),
+            b => { let _ = write!(out, "%{b:02X}"); }
+        }
+    }
+}
+
+impl BoardId {
+    /// Renders the [`BoardId`] as a URL query string, e.g.
+    /// `board_name=PRIME%20B550&board_vendor=ASUS&board_version=1.0`.
+    ///
+    /// The keys are the DMI attribute names, sorted, the values are percent-encoded, and undetected
+    /// parts are omitted, so the result is deterministic.
+    pub fn to_query_string(&self) -> String {
+        let mut query = String::new();
+        // sorted by key
+        for (field, value) in [(DmiField::BoardName, self.name()), (DmiField::BoardVendor, self.vendor()), (DmiField::BoardVersion, self.version())] {
+            let Some(value) = value else { continue };
+            if !query.is_empty() { query.push('&') }
+            query.push_str(field.attribute());
+            query.push('=');
+            percent_encode(&mut query, value);
+        }
+        query
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sorted() {
+        let board = BoardId::from_parts(Some(b"ASUS"), Some(b"PRIME"), Some(b"1.0")).unwrap();
+        assert_eq!(board.to_query_string(), "board_name=PRIME&board_vendor=ASUS&board_version=1.0");
+    }
+
+    #[test]
+    fn encoded() {
+        let board = BoardId::from_parts(Some(b"A&B Co."), Some(b"X=1 Y+2"), None).unwrap();
+        assert_eq!(board.to_query_string(), "board_name=X%3D1%20Y%2B2&board_vendor=A%26B%20Co.");
+    }
+
+    #[test]
+    fn undetected() {
+        assert_eq!(BoardId::from_parts(None, None, None).unwrap().to_query_string(), "");
+    }
+}
@@ -0,0 +1,86 @@
+//! [`BoardId::canonical_model`], mapping messy identities to canonical model names.
+
+use alloc::{string::{String, ToString}, vec::Vec};
+
+use crate::{glob_match, BoardId, Part};
+
+/// The quirks table, as documented in its header.
+const QUIRKS: &str = include_str!("../data/quirks.txt");
+
+/// Gets the quirks table entries, as their vendor glob, name glob and canonical model.
+fn quirks() -> impl Iterator<Item = [&'static str; 3]> {
+    QUIRKS.lines()
+        .filter(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '|');
+            Some([fields.next()?, fields.next()?, fields.next()?])
+        })
+}
+
+impl BoardId {
+    /// Gets the board's canonical model name.
+    ///
+    /// It's looked up in the quirks table shipped in `data/quirks.txt` (its header documents the
+    /// format, contributions welcome), which maps (vendor glob, name glob) pairs to canonical model
+    /// names, e.g. "X570 AORUS ELITE -CF" to "X570 AORUS ELITE". Boards not in the table fall back
+    /// to their [canonical](Self::part_canonical) name with whitespace runs collapsed into a space
+    /// and trimmed, or `None` if there's no such name.
+    pub fn canonical_model(&self) -> Option<String> {
+        let part = |part| self.part_canonical(part).unwrap_or_default().to_ascii_lowercase();
+        let (vendor, name) = (part(Part::Vendor), part(Part::Name));
+        let quirk = quirks().find(|[vendor_glob, name_glob, _]| {
+            glob_match(vendor_glob.to_ascii_lowercase().as_bytes(), &vendor)
+                && glob_match(name_glob.to_ascii_lowercase().as_bytes(), &name)
+        });
+        if let Some([_, _, model]) = quirk { return Some(model.to_string()) }
+        let name = String::from_utf8_lossy(self.part_canonical(Part::Name)?);
+        Some(name.split_whitespace().collect::<Vec<_>>().join(" ")).filter(|name| !name.is_empty())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    fn board(vendor: &str, name: &str) -> BoardId {
+        BoardId::from_parts(Some(vendor.as_bytes()), Some(name.as_bytes()), None).unwrap()
+    }
+
+    #[test]
+    fn table() {
+        let lines = QUIRKS.lines().filter(|line| !line.trim().is_empty() && !line.starts_with('#'));
+        assert_eq!(lines.count(), quirks().count(), "malformed line");
+        assert!(quirks().all(|[vendor, name, model]| !vendor.is_empty() && !name.is_empty() && !model.trim().is_empty()));
+    }
+
+    #[test]
+    fn shipped() {
+        let gigabyte = "Gigabyte Technology Co., Ltd.";
+        assert_eq!(board(gigabyte, "X570 AORUS ELITE").canonical_model().as_deref(), Some("X570 AORUS ELITE"));
+        assert_eq!(board(gigabyte, "X570 AORUS ELITE -CF").canonical_model().as_deref(), Some("X570 AORUS ELITE"));
+        assert_eq!(board(gigabyte, "B550 AORUS ELITE ").canonical_model().as_deref(), Some("B550 AORUS ELITE"));
+        assert_eq!(board("Micro-Star International Co., Ltd.", "MS-7C91").canonical_model().as_deref(), Some("MAG B550 TOMAHAWK"));
+        assert_eq!(BoardId::from_fixture("lenovo_thinkpad").unwrap().canonical_model().as_deref(), Some("ThinkPad T14 Gen 2i"));
+    }
+
+    #[test]
+    fn distinct_boards() {
+        let gigabyte = "Gigabyte Technology Co., Ltd.";
+        for name in ["X570 AORUS ELITE WIFI", "B550 AORUS ELITE AX V2", "B550 AORUS ELITE V2"] {
+            assert_eq!(board(gigabyte, name).canonical_model().as_deref(), Some(name));
+        }
+    }
+
+    #[test]
+    fn case_insensitive() {
+        assert_eq!(board("GIGABYTE", "x570 aorus elite-cf").canonical_model().as_deref(), Some("X570 AORUS ELITE"));
+        assert_eq!(board("lenovo", "20xwcto1ww").canonical_model().as_deref(), Some("ThinkPad T14 Gen 2i"));
+    }
+
+    #[test]
+    fn fallback() {
+        assert_eq!(board("ASUSTeK COMPUTER INC.", " PRIME  B550-PLUS ").canonical_model().as_deref(), Some("PRIME B550-PLUS"));
+        assert_eq!(BoardId::from_fixture("whitebox").unwrap().canonical_model(), None);
+        assert_eq!(BoardId::from_fixture("undetected").unwrap().canonical_model(), None);
+    }
+}
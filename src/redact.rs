@@ -0,0 +1,73 @@
+//! [`BoardId::redacted`], displaying a [`BoardId`] with some parts hidden.
+
+use core::fmt::{self, Display};
+
+use crate::{fmt_parts, BoardId, Part};
+
+/// Which parts [`BoardId::redacted`] hides.
+#[derive(Debug, Default, Hash, Clone, Copy, PartialEq, Eq)]
+pub struct RedactMask {
+    /// Whether to hide the vendor.
+    pub vendor: bool,
+    /// Whether to hide the name.
+    pub name: bool,
+    /// Whether to hide the version.
+    pub version: bool,
+}
+
+impl RedactMask {
+    /// Checks whether the mask hides the part.
+    pub fn hides(self, part: Part) -> bool {
+        match part {
+            Part::Vendor  => self.vendor,
+            Part::Name    => self.name,
+            Part::Version => self.version,
+        }
+    }
+}
+
+/// A [`BoardId`] displayed with some parts hidden, as per [`BoardId::redacted`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Redacted<'a> {
+    /// The board.
+    board: &'a BoardId,
+    /// The hidden parts.
+    mask: RedactMask,
+}
+
+/// Same as [`BoardId`]'s [`Display`] implementation, but with the hidden parts replaced by `***`.
+impl Display for Redacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [vendor, name, version] = Part::ALL.map(|part| {
+            self.board.part(part).map(|value| if self.mask.hides(part) { b"***" } else { value })
+        });
+        fmt_parts(f, vendor, name, version)
+    }
+}
+
+impl BoardId {
+    /// Gets a [`Display`] of the [`BoardId`] with the parts the mask selects replaced by `***`, e.g.
+    /// for screenshots and demos.
+    ///
+    /// Undetected parts display as usual, since there's nothing to hide.
+    pub fn redacted(&self, mask: RedactMask) -> Redacted<'_> { Redacted { board: self, mask } }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn name() {
+        let board = BoardId::from_parts(Some(b"ASUS"), Some(b"PRIME B550"), Some(b"1.0")).unwrap();
+        assert_eq!(board.redacted(RedactMask { name: true, ..RedactMask::default() }).to_string(), "ASUS *** 1.0");
+        assert_eq!(board.redacted(RedactMask::default()).to_string(), board.to_string());
+        assert_eq!(board.redacted(RedactMask { vendor: true, name: true, version: true }).to_string(), "*** *** ***");
+    }
+
+    #[test]
+    fn undetected() {
+        let board = BoardId::from_parts(Some(b"ASUS"), None, None).unwrap();
+        assert_eq!(board.redacted(RedactMask { vendor: true, name: true, version: true }).to_string(), "*** motherboard");
+    }
+}
@@ -0,0 +1,59 @@
+//! [`BoardId::from_fixture`], canned [`BoardId`]s for hermetic tests.
+
+use crate::BoardId;
+
+/// The fixtures, by name, as their vendor, name and version lines (empty if undetected).
+const FIXTURES: [(&str, &str); 7] = [
+    ("asus_prime"     , include_str!("../fixtures/asus_prime")),
+    ("dell_optiplex"  , include_str!("../fixtures/dell_optiplex")),
+    ("gigabyte_aorus" , include_str!("../fixtures/gigabyte_aorus")),
+    ("lenovo_thinkpad", include_str!("../fixtures/lenovo_thinkpad")),
+    ("raspberry_pi_4" , include_str!("../fixtures/raspberry_pi_4")),
+    ("whitebox"       , include_str!("../fixtures/whitebox")),
+    ("undetected"     , include_str!("../fixtures/undetected")),
+];
+
+impl BoardId {
+    /// Gets a canned [`BoardId`] by name, for testing without real hardware, or `None` if there's
+    /// no such fixture.
+    ///
+    /// | Name              | Vendor                          | Name                             | Version            |
+    /// |-------------------|---------------------------------|----------------------------------|--------------------|
+    /// | `asus_prime`      | `ASUSTeK COMPUTER INC.`         | `PRIME B550-PLUS`                | `Rev X.0x`         |
+    /// | `dell_optiplex`   | `Dell Inc.`                     | `0XR032`                         | `A00`              |
+    /// | `gigabyte_aorus`  | `Gigabyte Technology Co., Ltd.` | `X570 AORUS ELITE`               | `x.x`              |
+    /// | `lenovo_thinkpad` | `LENOVO`                        | `20XWCTO1WW`                     | `SDK0J40697 WIN`   |
+    /// | `raspberry_pi_4`  | `Raspberry Pi`                  | `Raspberry Pi 4 Model B Rev 1.4` | undetected         |
+    /// | `whitebox`        | `To be filled by O.E.M.`        | `To be filled by O.E.M.`         | `Default string`   |
+    /// | `undetected`      | undetected                      | undetected                       | undetected         |
+    pub fn from_fixture(name: &str) -> Option<Self> {
+        let (_, lines) = FIXTURES.iter().find(|(fixture, _)| *fixture == name)?;
+        let mut lines = lines.lines().map(|line| Some(line.as_bytes()).filter(|line| !line.is_empty()));
+        let [vendor, name, version] = [(); 3].map(|()| lines.next().flatten());
+        Self::from_parts(vendor, name, version).ok()
+    }
+
+    /// Gets the names of the [fixtures](Self::from_fixture).
+    pub fn fixture_names() -> impl Iterator<Item = &'static str> { FIXTURES.iter().map(|&(name, _)| name) }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn asus_prime() {
+        let board = BoardId::from_fixture("asus_prime").unwrap();
+        assert_eq!(board.vendor(), Some(&b"ASUSTeK COMPUTER INC."[..]));
+        assert_eq!(board.name(), Some(&b"PRIME B550-PLUS"[..]));
+        assert_eq!(board.version(), Some(&b"Rev X.0x"[..]));
+    }
+
+    #[test]
+    fn all() {
+        assert!(BoardId::fixture_names().all(|name| BoardId::from_fixture(name).is_some()));
+        assert_eq!(BoardId::from_fixture("raspberry_pi_4").unwrap().version(), None);
+        assert_eq!(BoardId::from_fixture("undetected").unwrap(), BoardId::from_parts(None, None, None).unwrap());
+        assert_eq!(BoardId::from_fixture("nonexistent"), None);
+    }
+}
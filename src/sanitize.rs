@@ -0,0 +1,59 @@
+//! [`BoardId::sanitized`], and detecting the raw and sanitized [`BoardId`]s together.
+
+#[cfg(feature = "std")] use std::{io, path::Path};
+
+use crate::BoardId;
+
+impl BoardId {
+    /// Makes a sanitized [`BoardId`]: its whitespace [collapsed](Self::collapse_whitespace), its
+    /// [placeholder](crate::is_placeholder) parts undetected, and the rest trimmed of the leading
+    /// and trailing space left by collapsing. Parts that are only whitespace are undetected.
+    pub fn sanitized(&self) -> BoardId {
+        let collapsed = self.collapse_whitespace();
+        Self::repack(|part| collapsed.part_canonical(part).unwrap_or_default().trim_ascii().iter().copied())
+    }
+
+    /// Attempts to detect the [`BoardId`], returning it byte-exact along with its
+    /// [sanitized](Self::sanitized) version, e.g. to log the former and act on the latter.
+    ///
+    /// See [`Self::detect_pair_at`].
+    #[cfg(feature = "std")]
+    pub fn detect_pair() -> io::Result<(Self, Self)> { Self::detect_pair_at("/") }
+
+    /// Attempts to detect the [`BoardId`] of the system whose filesystem is rooted at the given
+    /// path, returning it byte-exact along with its [sanitized](Self::sanitized) version.
+    ///
+    /// See [`Self::detect_at`].
+    #[cfg(feature = "std")]
+    pub fn detect_pair_at(root: impl AsRef<Path>) -> io::Result<(Self, Self)> {
+        let raw = Self::detect_at(root)?;
+        Ok((raw, raw.sanitized()))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    #[test]
+    fn sanitized() {
+        let board = BoardId::from_parts(Some(b"System  manufacturer"), Some(b"PRIME   Z790-P\t"), Some(b"Rev 1.xx")).unwrap();
+        assert_eq!(board.sanitized(), BoardId::from_parts(None, Some(b"PRIME Z790-P"), Some(b"Rev 1.xx")).unwrap());
+        let padded = BoardId::from_parts(Some(b" \t "), Some(b"\tPRIME  B550 "), None).unwrap();
+        assert_eq!(padded.sanitized().as_tuple(), (None, Some(&b"PRIME B550"[..]), None));
+        let sanitized = BoardId::from_parts(Some(b"ASUS"), Some(b"PRIME"), None).unwrap();
+        assert_eq!(sanitized.sanitized(), sanitized);
+    }
+
+    #[test]
+    fn pair() {
+        let root = TempDir::new("sanitize-pair");
+        root.write("sys/class/dmi/id/board_vendor", "ASUSTeK COMPUTER INC.\n");
+        root.write("sys/class/dmi/id/board_name", "PRIME  B550-PLUS\n");
+        root.write("sys/class/dmi/id/board_version", "To be filled by O.E.M.\n");
+        let (raw, sanitized) = BoardId::detect_pair_at(root.path()).unwrap();
+        assert_eq!(raw.as_tuple(), (Some(&b"ASUSTeK COMPUTER INC."[..]), Some(&b"PRIME  B550-PLUS"[..]), Some(&b"To be filled by O.E.M."[..])));
+        assert_eq!(sanitized.as_tuple(), (Some(&b"ASUSTeK COMPUTER INC."[..]), Some(&b"PRIME B550-PLUS"[..]), None));
+    }
+}
@@ -0,0 +1,101 @@
+//! [`SbcInfo`], structured info on well-known single-board computers.
+
+use crate::BoardId;
+
+/// Structured info on a single-board computer.
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub struct SbcInfo {
+    /// The vendor, e.g. "Orange Pi".
+    pub vendor: &'static str,
+    /// The model, e.g. "5".
+    pub model: &'static str,
+    /// The SoC, e.g. "RK3588".
+    pub soc: &'static str,
+}
+
+/// A known board, as its devicetree compatible, devicetree model, vendor, model and SoC.
+type Row = (&'static str, &'static str, &'static str, &'static str, &'static str);
+
+/// The known boards.
+const BOARDS: &[Row] = &include!("../data/sbc.in");
+
+/// The Raspberry Pi board types, as their revision code type field and their compatible.
+const RPI_REVISIONS: &[(u32, &str)] = &include!("../data/rpi_revisions.in");
+
+/// Finds the row whose key (per the given function) is the longest one that's a prefix of the value
+/// ending at a boundary (the end of the value or the given separator).
+fn longest_match(value: &[u8], separator: u8, key: impl Fn(&Row) -> &str) -> Option<SbcInfo> {
+    BOARDS.iter()
+        .filter(|row| {
+            let key = key(row).as_bytes();
+            value.starts_with(key) && value.get(key.len()).is_none_or(|&b| b == separator)
+        })
+        .max_by_key(|row| key(row).len())
+        .map(|&(_, _, vendor, model, soc)| SbcInfo { vendor, model, soc })
+}
+
+impl SbcInfo {
+    /// Looks up a board by its [`BoardId`], as detected from the devicetree, by its name (the
+    /// devicetree `model` property).
+    ///
+    /// Known models match names they prefix at a word boundary (e.g. "Raspberry Pi 4 Model B" matches
+    /// "Raspberry Pi 4 Model B Rev 1.4"), and the longest one wins.
+    pub fn lookup(board: &BoardId) -> Option<Self> { longest_match(board.name()?, b' ', |row| row.1) }
+
+    /// Looks up a board by its devicetree `compatible` property: a NUL-separated list of
+    /// compatibles, most specific first (a single compatible works too).
+    ///
+    /// The first compatible that's known wins. Known compatibles match compatibles they prefix at a
+    /// `-` boundary (e.g. "xunlong,orangepi-5" matches "xunlong,orangepi-5-rev2" but not
+    /// "xunlong,orangepi-5b"), and the longest one wins.
+    pub fn lookup_compatible(compatible: &[u8]) -> Option<Self> {
+        compatible.split(|&b| b == 0).find_map(|compatible| longest_match(compatible, b'-', |row| row.0))
+    }
+
+    /// Looks up a Raspberry Pi by its revision code (the `Revision` of `/proc/cpuinfo`, e.g. 0xC03114).
+    ///
+    /// Only new-style revision codes (bit 23 set) are supported.
+    pub fn lookup_rpi_revision(code: u32) -> Option<Self> {
+        if code & (1 << 23) == 0 { return None }
+        let board_type = (code >> 4) & 0xFF;
+        let &(_, compatible) = RPI_REVISIONS.iter().find(|&&(t, _)| t == board_type)?;
+        Self::lookup_compatible(compatible.as_bytes())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    const ORANGE_PI_5: SbcInfo = SbcInfo { vendor: "Orange Pi", model: "5", soc: "RK3588" };
+    const RPI_4: SbcInfo = SbcInfo { vendor: "Raspberry Pi", model: "4 Model B", soc: "BCM2711" };
+
+    #[test]
+    fn hit() {
+        assert_eq!(SbcInfo::lookup_compatible(b"rockchip,rk3588-orangepi-5"), Some(ORANGE_PI_5));
+        assert_eq!(SbcInfo::lookup_compatible(b"raspberrypi,4-model-b\0brcm,bcm2711\0"), Some(RPI_4));
+        assert_eq!(SbcInfo::lookup(&BoardId::from_fixture("raspberry_pi_4").unwrap()), Some(RPI_4));
+        assert_eq!(SbcInfo::lookup_rpi_revision(0xC03114), Some(RPI_4));
+        assert_eq!(SbcInfo::lookup_rpi_revision(0xD04170).map(|info| info.soc), Some("BCM2712"));
+    }
+
+    #[test]
+    fn miss() {
+        assert_eq!(SbcInfo::lookup_compatible(b"acme,unknown-board\0"), None);
+        assert_eq!(SbcInfo::lookup_compatible(b""), None);
+        assert_eq!(SbcInfo::lookup(&BoardId::from_fixture("asus_prime").unwrap()), None);
+        assert_eq!(SbcInfo::lookup(&BoardId::from_fixture("undetected").unwrap()), None);
+        assert_eq!(SbcInfo::lookup_rpi_revision(0x000E), None, "old-style code");
+        assert_eq!(SbcInfo::lookup_rpi_revision(0xC03FF0), None, "unknown type");
+    }
+
+    #[test]
+    fn prefix() {
+        assert_eq!(SbcInfo::lookup_compatible(b"raspberrypi,4-model-b-rev2"), Some(RPI_4));
+        assert_eq!(SbcInfo::lookup_compatible(b"xunlong,orangepi-5-plus").map(|info| info.model), Some("5 Plus"));
+        assert_eq!(SbcInfo::lookup_compatible(b"xunlong,orangepi-5b"), None);
+        assert_eq!(SbcInfo::lookup_compatible(b"xunlong,orangepi-5b\0xunlong,orangepi-5\0").map(|info| info.soc), Some("RK3588S"));
+        let plus = BoardId::from_parts(Some(b"xunlong"), Some(b"Orange Pi 5 Plus"), None).unwrap();
+        assert_eq!(SbcInfo::lookup(&plus).map(|info| info.model), Some("5 Plus"));
+    }
+}
@@ -0,0 +1,260 @@
+//! [`ScanSession`], for detecting many [`BoardId`]s while reusing allocations.
+
+use std::{io, path::{Path, PathBuf}};
+
+use crate::{devicetree, dmi, BoardId, DmiField, FsOpener, Opener};
+
+/// How a data source's files are read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SourceKind {
+    /// The vendor, name and version DMI attributes.
+    Dmi,
+    /// The device tree `model` and `compatible` properties.
+    DeviceTree,
+}
+
+/// A data source of detection, in the order detection tries them.
+#[derive(Debug, Hash, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
+pub enum Source {
+    /// The sysfs DMI attributes (`/sys/class/dmi/id`).
+    Dmi,
+    /// The device tree as exposed by procfs (`/proc/device-tree`).
+    ProcDeviceTree,
+    /// The device tree as exposed by sysfs (`/sys/firmware/devicetree/base`).
+    SysfsDeviceTree,
+    /// The build-time override of the `build-override` feature (see [`BoardId::detect`]).
+    BuildOverride,
+}
+
+/// A data source's entry in the [`SOURCES`] table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SourceEntry {
+    /// The source.
+    pub source: Source,
+    /// How the files are read.
+    pub kind: SourceKind,
+    /// The directory, relative to the filesystem root.
+    pub dir: &'static str,
+    /// The files in the directory, in the order they're read.
+    pub files: &'static [&'static str],
+}
+
+/// The data sources, in the order detection tries them.
+///
+/// This is the single source of truth for both detection and [`probe_paths`].
+pub(crate) const SOURCES: [SourceEntry; 3] = {
+    const DMI: &[&str] = &[DmiField::BoardVendor.attribute(), DmiField::BoardName.attribute(), DmiField::BoardVersion.attribute()];
+    const DEVICE_TREE: &[&str] = &["model", "compatible"];
+    [
+        SourceEntry { source: Source::Dmi            , kind: SourceKind::Dmi       , dir: dmi::DIR           , files: DMI         },
+        SourceEntry { source: Source::ProcDeviceTree , kind: SourceKind::DeviceTree, dir: devicetree::DIRS[0], files: DEVICE_TREE },
+        SourceEntry { source: Source::SysfsDeviceTree, kind: SourceKind::DeviceTree, dir: devicetree::DIRS[1], files: DEVICE_TREE },
+    ]
+};
+
+/// Which data sources exist, as far as is known (`None` if unknown), as per [`SOURCES`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Sources(pub [Option<bool>; SOURCES.len()]);
+
+/// Gets the paths of the files detection would read, in the order it would try them.
+///
+/// Does no I/O, so it's suitable for explaining an undetected motherboard. See
+/// [`probe_paths_at`].
+pub fn probe_paths() -> Vec<PathBuf> { probe_paths_at("/") }
+
+/// Gets the paths of the files detection of the system rooted at the given path would read, in
+/// the order it would try them.
+///
+/// See [`probe_paths`].
+pub fn probe_paths_at(root: impl AsRef<Path>) -> Vec<PathBuf> {
+    let root = root.as_ref();
+    SOURCES.iter().flat_map(|source| source.files.iter().map(|file| root.join(source.dir).join(file))).collect()
+}
+
+/// Attempts to detect the [`BoardId`]s of the systems rooted at the given paths (e.g. mounted disk
+/// images), in order, each along with its root.
+///
+/// This is [`ScanSession::detect_many`] with a fresh session, pairing the results with the roots.
+/// Each root is detected as per [`BoardId::detect_at`], and a failure doesn't stop the rest. Files
+/// that are missing, broken symlinks or empty are undetected parts rather than failures.
+pub fn detect_many(roots: &[&Path]) -> Vec<(PathBuf, io::Result<BoardId>)> {
+    let results = ScanSession::new().detect_many(roots.iter().copied());
+    roots.iter().map(|&root| root.to_owned()).zip(results).collect()
+}
+
+/// Detects [`BoardId`]s of many systems (e.g. mounted images), reusing its scratch space across
+/// detections: the paths of the files to read, and the device tree properties. DMI attributes are
+/// read straight into the [`BoardId`]'s own buffer, so there's nothing to reuse for them.
+///
+/// [`BoardId::detect_at`] is a detection with a fresh session, so the two are equivalent.
+#[derive(Debug, Default, Clone)]
+pub struct ScanSession {
+    /// Scratch space for the paths of the files to read.
+    path: PathBuf,
+    /// Scratch space for the device tree `model` property.
+    model: Vec<u8>,
+    /// Scratch space for the device tree `compatible` property.
+    compatible: Vec<u8>,
+}
+
+impl ScanSession {
+    /// Creates a new session.
+    pub fn new() -> Self { Self::default() }
+
+    /// Attempts to detect the [`BoardId`] of the system whose filesystem is rooted at the given
+    /// path.
+    ///
+    /// See [`BoardId::detect_at`].
+    pub fn detect_at(&mut self, root: impl AsRef<Path>) -> io::Result<BoardId> {
+        Ok(self.detect_with(&mut FsOpener, root.as_ref(), None)?.0)
+    }
+
+    /// Attempts to detect the [`BoardId`] of the system whose filesystem is rooted at the given
+    /// path, along with the source it was detected from (`None` if undetected).
+    ///
+    /// See [`BoardId::detect_with_provenance_at`].
+    pub fn detect_with_provenance_at(&mut self, root: impl AsRef<Path>) -> io::Result<(BoardId, Option<Source>)> {
+        self.detect_with(&mut FsOpener, root.as_ref(), None)
+    }
+
+    /// Attempts to detect the [`BoardId`] of the system rooted at the given path, opening files
+    /// with the given opener.
+    ///
+    /// When given the known sources, the sources known to be absent are skipped, and the unknown
+    /// ones are probed for and remembered.
+    ///
+    /// Returns the source the [`BoardId`] was detected from too (`None` if undetected).
+    pub(crate) fn detect_with(&mut self, opener: &mut impl Opener, root: &Path, mut sources: Option<&mut Sources>) -> io::Result<(BoardId, Option<Source>)> {
+        let mut board = BoardId::from_parts(None, None, None)?;
+        for (i, source) in SOURCES.iter().enumerate() {
+            if !self.present(opener, root, source.dir, sources.as_deref_mut().map(|s| &mut s.0[i]))? { continue }
+            board = match source.kind {
+                SourceKind::Dmi => BoardId::from_streams(
+                    self.open(opener, root, source.dir, source.files[0])?,
+                    self.open(opener, root, source.dir, source.files[1])?,
+                    self.open(opener, root, source.dir, source.files[2])?,
+                )?,
+                SourceKind::DeviceTree => {
+                    let model = self.open(opener, root, source.dir, source.files[0])?;
+                    devicetree::read_property(model, &mut self.model)?;
+                    let compatible = self.open(opener, root, source.dir, source.files[1])?;
+                    devicetree::read_property(compatible, &mut self.compatible)?;
+                    BoardId::from_device_tree(&self.model, &self.compatible)?
+                }
+            };
+            if !board.is_undetected() { return Ok((board, Some(source.source))) }
+        }
+        Ok((board, None))
+    }
+
+    /// Checks whether the source directory `root/dir` should be read.
+    ///
+    /// Without a `known` presence to consult, it always should (its files are simply tried).
+    fn present(&mut self, opener: &mut impl Opener, root: &Path, dir: &str, known: Option<&mut Option<bool>>) -> io::Result<bool> {
+        let Some(known) = known else { return Ok(true) };
+        if let Some(present) = *known { return Ok(present) }
+        self.path.clear();
+        self.path.extend([root, dir.as_ref()]);
+        let present = opener.dir_exists(&self.path)?;
+        *known = Some(present);
+        Ok(present)
+    }
+
+    /// Attempts to detect the [`BoardId`]s of the systems rooted at the given paths, in order.
+    ///
+    /// See [`Self::detect_at`].
+    pub fn detect_many<'a>(&mut self, roots: impl Iterator<Item = &'a Path>) -> Vec<io::Result<BoardId>> {
+        roots.map(|root| self.detect_at(root)).collect()
+    }
+
+    /// Opens the file at `root/dir/file`, returning `Ok(None)` if it doesn't exist.
+    fn open<O: Opener>(&mut self, opener: &mut O, root: &Path, dir: &str, file: &str) -> io::Result<Option<O::File>> {
+        self.path.clear();
+        self.path.extend([root, dir.as_ref(), file.as_ref()]);
+        opener.open(&self.path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    /// Fixture roots covering each detection path.
+    fn roots() -> [TempDir; 4] {
+        let dmi = TempDir::new("scan-dmi");
+        dmi.write("sys/class/dmi/id/board_vendor", "VENDOR\n");
+        dmi.write("sys/class/dmi/id/board_name", "NAME\n");
+        dmi.write("sys/class/dmi/id/board_version", "VERSION\n");
+        let partial = TempDir::new("scan-partial");
+        partial.write("sys/class/dmi/id/board_name", "OTHER\n");
+        let device_tree = TempDir::new("scan-device-tree");
+        device_tree.write("proc/device-tree/model", "MODEL\0");
+        device_tree.write("proc/device-tree/compatible", "vendor,model\0");
+        let empty = TempDir::new("scan-empty");
+        [dmi, partial, device_tree, empty]
+    }
+
+    #[test]
+    fn reused() {
+        let roots = roots();
+        let expected = [
+            BoardId::from_parts(Some(b"VENDOR"), Some(b"NAME"), Some(b"VERSION")).unwrap(),
+            BoardId::from_parts(None, Some(b"OTHER"), None).unwrap(),
+            BoardId::from_parts(Some(b"vendor"), Some(b"MODEL"), None).unwrap(),
+            BoardId::from_parts(None, None, None).unwrap(),
+        ];
+        let mut session = ScanSession::new();
+        // twice over, so each root is scanned after every other one
+        for (root, expected) in roots.iter().zip(&expected).chain(roots.iter().zip(&expected)) {
+            assert_eq!(session.detect_at(root.path()).unwrap(), *expected, "{}", root.path().display());
+        }
+    }
+
+    #[test]
+    fn detect_many() {
+        let roots = roots();
+        let results = ScanSession::new().detect_many(roots.iter().map(TempDir::path));
+        let names = results.into_iter().map(|board| board.unwrap().name().map(<[u8]>::to_vec)).collect::<Vec<_>>();
+        assert_eq!(names, [Some(b"NAME".to_vec()), Some(b"OTHER".to_vec()), Some(b"MODEL".to_vec()), None]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn detect_many_roots() {
+        let [dmi, ..] = roots();
+        let degraded = TempDir::new("scan-degraded");
+        degraded.write("sys/class/dmi/id/board_name", "");
+        std::os::unix::fs::symlink(degraded.path().join("missing"), degraded.path().join("sys/class/dmi/id/board_vendor")).unwrap();
+        degraded.write("sys/class/dmi/id/board_version", "1.0\n");
+        let failing = TempDir::new("scan-failing");
+        std::fs::create_dir_all(failing.path().join("sys/class/dmi/id/board_vendor")).unwrap();
+        let results = super::detect_many(&[failing.path(), degraded.path(), dmi.path()]);
+        assert_eq!(results.iter().map(|(root, _)| root.as_path()).collect::<Vec<_>>(), [failing.path(), degraded.path(), dmi.path()]);
+        assert!(results[0].1.is_err());
+        assert_eq!(results[1].1.as_ref().unwrap().as_tuple(), (None, None, Some(&b"1.0"[..])));
+        assert_eq!(results[2].1.as_ref().unwrap().to_string(), "VENDOR NAME VERSION");
+    }
+
+    #[test]
+    fn provenance() {
+        let roots = roots();
+        let sources = roots.iter().map(|root| BoardId::detect_with_provenance_at(root.path()).unwrap().1).collect::<Vec<_>>();
+        assert_eq!(sources, [Some(Source::Dmi), Some(Source::Dmi), Some(Source::ProcDeviceTree), None]);
+        let sysfs = TempDir::new("scan-sysfs-device-tree");
+        sysfs.write("sys/firmware/devicetree/base/model", "MODEL\0");
+        let (board, source) = BoardId::detect_with_provenance_at(sysfs.path()).unwrap();
+        assert_eq!((board.name(), source), (Some(&b"MODEL"[..]), Some(Source::SysfsDeviceTree)));
+    }
+
+    #[test]
+    fn probe_paths() {
+        let paths = probe_paths_at("/root");
+        assert_eq!(paths.len(), 7);
+        assert_eq!(paths[0], Path::new("/root/sys/class/dmi/id/board_vendor"));
+        assert_eq!(paths[2], Path::new("/root/sys/class/dmi/id/board_version"));
+        assert_eq!(paths[4], Path::new("/root/proc/device-tree/compatible"));
+        assert_eq!(paths[6], Path::new("/root/sys/firmware/devicetree/base/compatible"));
+        assert_eq!(super::probe_paths()[0], Path::new("/sys/class/dmi/id/board_vendor"));
+    }
+}
@@ -0,0 +1,67 @@
+//! Case-insensitive substring search in [`BoardId`]s.
+
+use crate::{BoardId, Part};
+
+/// Checks whether the haystack contains the needle, ASCII case-insensitively.
+fn contains_ignore_ascii_case(haystack: &[u8], needle: &[u8]) -> bool {
+    needle.is_empty() || haystack.windows(needle.len()).any(|window| window.eq_ignore_ascii_case(needle))
+}
+
+impl BoardId {
+    /// Checks whether any detected part contains the needle, ASCII case-insensitively, e.g. for
+    /// interactive filtering.
+    ///
+    /// Matches don't span parts. An empty needle is always contained. Doesn't allocate.
+    pub fn contains(&self, needle: &[u8]) -> bool {
+        needle.is_empty() || Part::ALL.into_iter().any(|part| self.contains_in(part, needle))
+    }
+
+    /// Checks whether the part contains the needle, ASCII case-insensitively.
+    ///
+    /// An undetected part contains nothing but the empty needle. Doesn't allocate.
+    pub fn contains_in(&self, part: Part, needle: &[u8]) -> bool {
+        contains_ignore_ascii_case(self.part(part).unwrap_or_default(), needle)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parts() {
+        let board = BoardId::from_parts(Some(b"ASUS"), Some(b"PRIME B550-PLUS"), None).unwrap();
+        assert!( board.contains(b"b550"));
+        assert!( board.contains(b"Asus"));
+        assert!( board.contains(b""));
+        assert!(!board.contains(b"SUSPRIME"), "matched across parts");
+        assert!( board.contains_in(Part::Name, b"prime"));
+        assert!(!board.contains_in(Part::Vendor, b"prime"));
+        assert!( board.contains_in(Part::Version, b""));
+        assert!(!board.contains_in(Part::Version, b"a"));
+    }
+
+    #[test]
+    fn naive() {
+        fn naive(haystack: &[u8], needle: &[u8]) -> bool {
+            let (haystack, needle) = (haystack.to_ascii_lowercase(), needle.to_ascii_lowercase());
+            (0..=haystack.len()).any(|i| haystack[i..].starts_with(&needle))
+        }
+
+        // a small alphabet with mixed case and non-ASCII, so matches are frequent
+        const ALPHABET: &[u8] = b"aAbB5-\xC3\xA9";
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+        let mut random = |max: usize| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as usize % max
+        };
+        for _ in 0..2000 {
+            let mut string = |max| { let len = random(max); (0..len).map(|_| ALPHABET[random(ALPHABET.len())]).collect::<Vec<_>>() };
+            let (haystack, needle) = (string(12), string(4));
+            let board = BoardId::from_parts(None, Some(&haystack), None).unwrap();
+            assert_eq!(board.contains_in(Part::Name, &needle), naive(&haystack, &needle), "{haystack:?} {needle:?}");
+        }
+    }
+}
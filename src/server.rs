@@ -0,0 +1,102 @@
+//! Server-class hardware detection.
+
+use std::{fs, io, path::Path};
+
+use crate::{dmi, BoardId, DmiField};
+
+/// SMBIOS chassis types (the `chassis_type` DMI attribute) of server-class hardware:
+/// - 17: Main Server Chassis
+/// - 23: Rack Mount Chassis
+/// - 25: Multi-system Chassis
+/// - 28: Blade
+/// - 29: Blade Enclosure
+pub(crate) const SERVER_CHASSIS_TYPES: [u8; 5] = [17, 23, 25, 28, 29];
+
+/// SMBIOS baseboard types of server-class hardware:
+/// - 3: Server Blade
+const SERVER_BOARD_TYPES: [u8; 1] = [3];
+
+/// The raw SMBIOS baseboard (type 2) structure, relative to the filesystem root.
+const BOARD_ENTRY: &str = "sys/firmware/dmi/entries/2-0/raw";
+
+/// The offset of the board type in the SMBIOS baseboard structure.
+const BOARD_TYPE_OFFSET: usize = 0x0D;
+
+impl BoardId {
+    /// Checks whether the hardware is server-class.
+    ///
+    /// It is if either:
+    /// - The SMBIOS chassis type is Main Server Chassis (17), Rack Mount Chassis (23),
+    ///   Multi-system Chassis (25), Blade (28) or Blade Enclosure (29).
+    /// - The SMBIOS baseboard type is Server Blade (3). sysfs doesn't expose the board type as an
+    ///   attribute, so it's read from the raw SMBIOS baseboard structure, which is typically
+    ///   root-only; it's skipped if it can't be read.
+    pub fn is_server_class() -> io::Result<bool> { Self::is_server_class_at("/") }
+
+    /// Checks whether the hardware of the system whose filesystem is rooted at the given path is
+    /// server-class.
+    ///
+    /// See [`Self::is_server_class`].
+    pub fn is_server_class_at(root: impl AsRef<Path>) -> io::Result<bool> {
+        let root = root.as_ref();
+        let chassis_type = dmi::read_attribute(root, DmiField::ChassisType)?
+            .and_then(|t| std::str::from_utf8(&t).ok()?.trim().parse::<u8>().ok());
+        if chassis_type.is_some_and(|t| SERVER_CHASSIS_TYPES.contains(&(t & 0x7F))) { return Ok(true) }
+        let board_type = fs::read(root.join(BOARD_ENTRY)).ok()
+            .and_then(|entry| entry.get(BOARD_TYPE_OFFSET).copied().filter(|_| entry.get(1).is_some_and(|&len| len as usize > BOARD_TYPE_OFFSET)));
+        Ok(board_type.is_some_and(|t| SERVER_BOARD_TYPES.contains(&t)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    /// A baseboard structure with the given board type.
+    fn board_entry(board_type: u8) -> [u8; 15] {
+        let mut entry = [0u8; 15];
+        entry[0] = 2;
+        entry[1] = 15;
+        entry[BOARD_TYPE_OFFSET] = board_type;
+        entry
+    }
+
+    #[test]
+    fn rack_mount() {
+        let root = TempDir::new("server-rack-mount");
+        root.write("sys/class/dmi/id/chassis_type", "23\n");
+        assert!(BoardId::is_server_class_at(root.path()).unwrap());
+    }
+
+    #[test]
+    fn laptop() {
+        let root = TempDir::new("server-laptop");
+        root.write("sys/class/dmi/id/chassis_type", "10\n");
+        root.write(BOARD_ENTRY, board_entry(0x0A)); // Motherboard
+        assert!(!BoardId::is_server_class_at(root.path()).unwrap());
+    }
+
+    #[test]
+    fn server_blade_board() {
+        let root = TempDir::new("server-blade-board");
+        root.write("sys/class/dmi/id/chassis_type", "3\n");
+        root.write(BOARD_ENTRY, board_entry(0x03));
+        assert!(BoardId::is_server_class_at(root.path()).unwrap());
+    }
+
+    #[test]
+    fn short_board_entry() {
+        let root = TempDir::new("server-short-board-entry");
+        let mut entry = board_entry(0x03);
+        entry[1] = 8;
+        root.write(BOARD_ENTRY, entry);
+        assert!(!BoardId::is_server_class_at(root.path()).unwrap());
+    }
+
+    #[test]
+    fn nothing() {
+        let root = TempDir::new("server-nothing");
+        assert!(!BoardId::is_server_class_at(root.path()).unwrap());
+    }
+}
@@ -0,0 +1,74 @@
+//! [`BoardId::similarity`], for fuzzy grouping of near-identical boards.
+
+use alloc::{collections::BTreeSet, string::String};
+
+use crate::{BoardId, Part};
+
+impl BoardId {
+    /// Gets the board's tags: the lowercased alphanumeric tokens of its
+    /// [canonical](Self::part_canonical) parts, e.g. "asustek", "computer", "inc", "prime", "b550"
+    /// and "plus" for "ASUSTeK COMPUTER INC." "PRIME B550-PLUS".
+    pub fn tags(&self) -> BTreeSet<String> {
+        Part::ALL.into_iter()
+            .filter_map(|part| self.part_canonical(part))
+            .flat_map(|part| part.split(|b| !b.is_ascii_alphanumeric()))
+            .filter(|token| !token.is_empty())
+            .map(|token| String::from_utf8_lossy(token).to_ascii_lowercase())
+            .collect()
+    }
+
+    /// Gets how similar the boards are, from 0 (no common [tag](Self::tags)) to 1 (the same tags).
+    ///
+    /// It's the Jaccard index of the boards' tags: the number of tags they share divided by the
+    /// number of tags either has. It's symmetric, and two boards without tags are the same (1).
+    /// Since tags are taken from every part, boards that differ only in their version score high
+    /// but below 1.
+    pub fn similarity(&self, other: &BoardId) -> f32 { jaccard(&self.tags(), &other.tags()) }
+}
+
+/// Gets the Jaccard index of the tags, as per [`BoardId::similarity`].
+pub(crate) fn jaccard(tags: &BTreeSet<String>, other: &BTreeSet<String>) -> f32 {
+    let union = tags.union(other).count();
+    if union == 0 { return 1.0 }
+    tags.intersection(other).count() as f32 / union as f32
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    fn board(vendor: &str, name: &str, version: &str) -> BoardId {
+        BoardId::from_parts(Some(vendor.as_bytes()), Some(name.as_bytes()), Some(version.as_bytes())).unwrap()
+    }
+
+    #[test]
+    fn tags() {
+        let tags = BoardId::from_fixture("asus_prime").unwrap().tags();
+        assert_eq!(tags.iter().map(String::as_str).collect::<Vec<_>>(), ["0x", "asustek", "b550", "computer", "inc", "plus", "prime", "rev", "x"]);
+        assert!(BoardId::from_fixture("whitebox").unwrap().tags().is_empty());
+    }
+
+    #[test]
+    fn identical() {
+        let prime = BoardId::from_fixture("asus_prime").unwrap();
+        assert_eq!(prime.similarity(&prime), 1.0);
+        assert_eq!(board("ASUS", "PRIME B550", "1").similarity(&board("asus", "prime-b550", "1")), 1.0);
+        let undetected = BoardId::from_fixture("undetected").unwrap();
+        assert_eq!(undetected.similarity(&undetected), 1.0);
+    }
+
+    #[test]
+    fn disjoint() {
+        let (dell, pi) = (BoardId::from_fixture("dell_optiplex").unwrap(), BoardId::from_fixture("raspberry_pi_4").unwrap());
+        assert_eq!(dell.similarity(&pi), 0.0);
+        assert_eq!(dell.similarity(&BoardId::from_fixture("undetected").unwrap()), 0.0);
+    }
+
+    #[test]
+    fn partial() {
+        let (a, b) = (board("ASUS", "PRIME B550", "1.0"), board("ASUS", "PRIME B550", "2.0"));
+        // {asus, prime, b550, 1, 0} and {asus, prime, b550, 2, 0}
+        assert_eq!(a.similarity(&b), 4.0 / 6.0);
+        assert_eq!(a.similarity(&b), b.similarity(&a));
+    }
+}
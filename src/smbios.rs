@@ -0,0 +1,65 @@
+//! [`smbios_version`], the version of the SMBIOS specification the firmware implements.
+
+use std::{fs, io, path::Path};
+
+/// The SMBIOS entry point the kernel exposes, relative to the filesystem root.
+const ENTRY_POINT: &str = "sys/firmware/dmi/tables/smbios_entry_point";
+
+/// Parses the major and minor version out of an SMBIOS entry point.
+///
+/// The 32-bit (SMBIOS 2) entry point starts with `_SM_` and has the version at offsets 6 and 7,
+/// and the 64-bit (SMBIOS 3) one starts with `_SM3_` and has it at offsets 7 and 8.
+fn parse_entry_point(entry_point: &[u8]) -> Option<(u8, u8)> {
+    match entry_point {
+        [b'_', b'S', b'M', b'3', b'_', _, _, major, minor, ..] => Some((*major, *minor)),
+        [b'_', b'S', b'M', b'_', _, _, major, minor, ..] => Some((*major, *minor)),
+        _ => None,
+    }
+}
+
+/// Reads the SMBIOS version, as its major and minor numbers (e.g. `(3, 3)` for SMBIOS 3.3).
+///
+/// See [`smbios_version_at`].
+pub fn smbios_version() -> io::Result<Option<(u8, u8)>> { smbios_version_at("/") }
+
+/// Reads the SMBIOS version of the system whose filesystem is rooted at the given path, from its
+/// `sys/firmware/dmi/tables/smbios_entry_point`.
+///
+/// Returns `Ok(None)` if the entry point is absent (e.g. on systems without DMI) or unrecognized.
+/// The entry point is typically only readable by root.
+pub fn smbios_version_at(root: impl AsRef<Path>) -> io::Result<Option<(u8, u8)>> {
+    match fs::read(root.as_ref().join(ENTRY_POINT)) {
+        Ok(entry_point) => Ok(parse_entry_point(&entry_point)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    /// A 64-bit entry point of SMBIOS 3.3.
+    const SMBIOS_3: &[u8] = b"_SM3_\x8a\x18\x03\x03\x00\x01\x00\xa7\x0d\x00\x00\x00\xd0\x0d\x9b\x00\x00\x00\x00";
+
+    /// A 32-bit entry point of SMBIOS 2.8.
+    const SMBIOS_2: &[u8] = b"_SM_\x3d\x1f\x02\x08\x2d\x01\x00\x00\x00\x00\x00\x00_DMI_\x5b\x6e\x0a\x00\xe0\x0e\x00\x1e\x00\x28";
+
+    #[test]
+    fn parse() {
+        assert_eq!(parse_entry_point(SMBIOS_3), Some((3, 3)));
+        assert_eq!(parse_entry_point(SMBIOS_2), Some((2, 8)));
+        assert_eq!(parse_entry_point(b"_SM3_\x8a\x18\x03"), None, "truncated");
+        assert_eq!(parse_entry_point(b"_DMI_\x5b\x6e\x0a\x00"), None);
+        assert_eq!(parse_entry_point(b""), None);
+    }
+
+    #[test]
+    fn at_root() {
+        let root = TempDir::new("smbios-version");
+        assert_eq!(smbios_version_at(root.path()).unwrap(), None);
+        root.write(ENTRY_POINT, SMBIOS_3);
+        assert_eq!(smbios_version_at(root.path()).unwrap(), Some((3, 3)));
+    }
+}
@@ -0,0 +1,134 @@
+//! Snapshotting the [`BoardId`] at provisioning time, to verify later that the board hasn't changed.
+
+use core::hash::Hasher;
+use std::{fmt::{self, Display}, fs, io, path::Path};
+
+use crate::{cache::write_atomic, fingerprint::Fnv1a, pin::{ct_eq, PartValue}, BoardId, PartChange};
+
+/// The size of a snapshot's digest.
+const DIGEST_LEN: usize = 8;
+
+/// Gets the digest of a snapshot's encoding: its 64-bit FNV-1a hash, little-endian.
+fn digest(encoding: &[u8]) -> [u8; DIGEST_LEN] {
+    let mut hasher = Fnv1a::default();
+    hasher.write(encoding);
+    hasher.finish().to_le_bytes()
+}
+
+/// How a [snapshot](BoardId::snapshot_to) failed verification.
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub enum SnapshotMismatch {
+    /// The snapshot file isn't a snapshot (e.g. truncated).
+    Corrupt,
+    /// The snapshot's digest doesn't match its contents, so it was altered.
+    DigestMismatch,
+    /// The board changed since the snapshot: the parts that changed, old and new values.
+    Changed(Vec<PartChange>),
+}
+
+impl Display for SnapshotMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Corrupt        => write!(f, "the board snapshot is corrupt"),
+            Self::DigestMismatch => write!(f, "the board snapshot's digest doesn't match"),
+            Self::Changed(changes) => {
+                write!(f, "the board changed since its snapshot")?;
+                for change in changes { write!(f, "; {} was {}, now {}", change.part, PartValue(change.old.as_deref()), PartValue(change.new.as_deref()))? }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for SnapshotMismatch {}
+
+impl BoardId {
+    /// Writes a snapshot of the [`BoardId`] to the file at the given path, to [verify](Self::verify_snapshot)
+    /// against later.
+    ///
+    /// The snapshot is the [stable binary encoding](Self::to_bytes) followed by its digest (the
+    /// 64-bit FNV-1a hash, little-endian). The digest catches corruption and naive edits, but not
+    /// an attacker who recomputes it. The write is atomic, as per [`Self::store_cached`].
+    pub fn snapshot_to(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut snapshot = self.to_bytes();
+        snapshot.extend(digest(&snapshot));
+        write_atomic(path.as_ref(), &snapshot)
+    }
+
+    /// Detects the [`BoardId`] and verifies it against the snapshot at the given path.
+    ///
+    /// See [`Self::verify_snapshot_at`].
+    pub fn verify_snapshot(path: impl AsRef<Path>) -> io::Result<Result<(), SnapshotMismatch>> {
+        Self::verify_snapshot_at("/", path)
+    }
+
+    /// Detects the [`BoardId`] of the system whose filesystem is rooted at the given path and
+    /// verifies it against the snapshot at the given path (which isn't under the root).
+    ///
+    /// Fails if the snapshot can't be read or detection fails, and mismatches if the snapshot is
+    /// corrupt, its digest doesn't match, or the board changed. The values are compared in constant
+    /// time.
+    pub fn verify_snapshot_at(root: impl AsRef<Path>, path: impl AsRef<Path>) -> io::Result<Result<(), SnapshotMismatch>> {
+        let snapshot = fs::read(path)?;
+        let board = Self::detect_at(root)?;
+        Ok(Self::check_snapshot(&snapshot, &board))
+    }
+
+    /// Verifies the board against the snapshot.
+    fn check_snapshot(snapshot: &[u8], board: &BoardId) -> Result<(), SnapshotMismatch> {
+        let Some((encoding, stored)) = snapshot.split_last_chunk::<DIGEST_LEN>() else { return Err(SnapshotMismatch::Corrupt) };
+        if !ct_eq(Some(&digest(encoding)), Some(stored)) { return Err(SnapshotMismatch::DigestMismatch) }
+        let pinned = Self::from_bytes(encoding).map_err(|_| SnapshotMismatch::Corrupt)?;
+        let changes = PartChange::between(&pinned, board);
+        if changes.is_empty() { Ok(()) } else { Err(SnapshotMismatch::Changed(changes)) }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{fixture::TempDir, Part};
+
+    #[test]
+    fn verified() {
+        let root = TempDir::with_board("snapshot-verified");
+        let path = root.path().join("snapshot");
+        BoardId::detect_at(root.path()).unwrap().snapshot_to(&path).unwrap();
+        assert_eq!(BoardId::verify_snapshot_at(root.path(), &path).unwrap(), Ok(()));
+        assert_eq!(fs::read(&path).unwrap().len(), 4 + 10 + DIGEST_LEN);
+    }
+
+    #[test]
+    fn changed() {
+        let root = TempDir::with_board("snapshot-changed");
+        let path = root.path().join("snapshot");
+        BoardId::detect_at(root.path()).unwrap().snapshot_to(&path).unwrap();
+        root.write("sys/class/dmi/id/board_name", "OTHER\n");
+        assert_eq!(BoardId::verify_snapshot_at(root.path(), &path).unwrap(), Err(SnapshotMismatch::Changed(vec![
+            PartChange { part: Part::Name, old: Some(b"NAME".to_vec()), new: Some(b"OTHER".to_vec()) },
+        ])));
+        let mismatch = BoardId::verify_snapshot_at(root.path(), &path).unwrap().unwrap_err();
+        assert_eq!(mismatch.to_string(), "the board changed since its snapshot; name was \"NAME\", now \"OTHER\"");
+    }
+
+    #[test]
+    fn tampered() {
+        let root = TempDir::with_board("snapshot-tampered");
+        let path = root.path().join("snapshot");
+        BoardId::detect_at(root.path()).unwrap().snapshot_to(&path).unwrap();
+        let mut snapshot = fs::read(&path).unwrap();
+        snapshot[5] ^= 1;
+        fs::write(&path, &snapshot).unwrap();
+        assert_eq!(BoardId::verify_snapshot_at(root.path(), &path).unwrap(), Err(SnapshotMismatch::DigestMismatch));
+        fs::write(&path, &snapshot[..snapshot.len() - 1]).unwrap();
+        assert_eq!(BoardId::verify_snapshot_at(root.path(), &path).unwrap(), Err(SnapshotMismatch::DigestMismatch));
+        fs::write(&path, b"short").unwrap();
+        assert_eq!(BoardId::verify_snapshot_at(root.path(), &path).unwrap(), Err(SnapshotMismatch::Corrupt));
+        // a matching digest over an invalid encoding
+        let mut garbage = b"garbage".to_vec();
+        garbage.extend(digest(b"garbage"));
+        fs::write(&path, &garbage).unwrap();
+        assert_eq!(BoardId::verify_snapshot_at(root.path(), &path).unwrap(), Err(SnapshotMismatch::Corrupt));
+        assert_eq!(BoardId::verify_snapshot_at(root.path(), root.path().join("missing")).unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+}
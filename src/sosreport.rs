@@ -0,0 +1,115 @@
+//! Reading [`BoardId`]s out of extracted sosreports / support bundles.
+
+use std::{fs, io, path::{Path, PathBuf}};
+
+use crate::{open_existing_file, BoardId, DmiField};
+
+/// The locations of the copied DMI directory inside a bundle, in the order they're tried (layouts
+/// differ between sos versions).
+const DMI_DIRS: [&str; 2] = ["sys/class/dmi/id", "sys/devices/virtual/dmi/id"];
+
+/// The locations of the captured `dmidecode` output inside a bundle, in the order they're tried
+/// after [`DMI_DIRS`] (sos links the latter to the former).
+const DMIDECODE_FILES: [&str; 2] = ["sos_commands/hardware/dmidecode", "dmidecode"];
+
+/// The DMI attributes of the board, in vendor, name, version order.
+const FIELDS: [DmiField; 3] = [DmiField::BoardVendor, DmiField::BoardName, DmiField::BoardVersion];
+
+impl BoardId {
+    /// Reads the [`BoardId`] out of an extracted sosreport (or a similarly laid out support bundle),
+    /// along with the path of the file inside the bundle it was read from.
+    ///
+    /// The copied DMI directory is tried first, in each known layout, and the file is its first
+    /// board attribute that exists. A directory without board attributes is skipped. Failing that,
+    /// the captured `dmidecode` output is parsed (see [`Self::from_dmidecode_text`]).
+    ///
+    /// Fails with [`io::ErrorKind::NotFound`] if the path doesn't exist or if the bundle has no
+    /// motherboard ID, and with [`io::ErrorKind::Unsupported`] if the path isn't a directory
+    /// (compressed bundles aren't supported; extract them first).
+    pub fn from_sosreport(path: &Path) -> io::Result<(Self, PathBuf)> {
+        if !fs::metadata(path)?.is_dir() {
+            return Err(io::Error::new(io::ErrorKind::Unsupported, "the sosreport isn't an extracted directory"))
+        }
+        for dir in DMI_DIRS {
+            let files = FIELDS.map(|field| Path::new(dir).join(field.attribute()));
+            let [vendor, name, version] = files.each_ref().map(|file| open_existing_file(path.join(file)));
+            let (vendor, name, version) = (vendor?, name?, version?);
+            let Some(used) = [&vendor, &name, &version].iter().position(|file| file.is_some()) else { continue };
+            let board = Self::from_streams(vendor, name, version)?;
+            return Ok((board, files[used].clone()))
+        }
+        for file in DMIDECODE_FILES {
+            let text = match fs::read(path.join(file)) {
+                Ok(text) => text,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            };
+            if let Some(board) = Self::from_dmidecode_text(&String::from_utf8_lossy(&text))? {
+                return Ok((board, file.into()))
+            }
+        }
+        Err(io::Error::new(io::ErrorKind::NotFound, "no motherboard ID found in the sosreport"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    #[test]
+    fn class_dmi() {
+        let bundle = TempDir::new("sosreport-class");
+        bundle.write("sys/class/dmi/id/board_vendor", "VENDOR\n");
+        bundle.write("sys/class/dmi/id/board_name", "NAME\n");
+        let (board, file) = BoardId::from_sosreport(bundle.path()).unwrap();
+        assert_eq!(board, BoardId::from_parts(Some(b"VENDOR"), Some(b"NAME"), None).unwrap());
+        assert_eq!(file, Path::new("sys/class/dmi/id/board_vendor"));
+    }
+
+    #[test]
+    fn devices_virtual() {
+        let bundle = TempDir::new("sosreport-virtual");
+        bundle.write("sys/devices/virtual/dmi/id/board_name", "NAME\n");
+        let (board, file) = BoardId::from_sosreport(bundle.path()).unwrap();
+        assert_eq!(board.name(), Some(&b"NAME"[..]));
+        assert_eq!(file, Path::new("sys/devices/virtual/dmi/id/board_name"));
+    }
+
+    #[test]
+    fn no_board_attributes() {
+        let bundle = TempDir::new("sosreport-no-board");
+        bundle.write("sys/class/dmi/id/product_name", "PRODUCT\n");
+        bundle.write("sys/devices/virtual/dmi/id/board_name", "NAME\n");
+        let (board, file) = BoardId::from_sosreport(bundle.path()).unwrap();
+        assert_eq!(board.name(), Some(&b"NAME"[..]));
+        assert_eq!(file, Path::new("sys/devices/virtual/dmi/id/board_name"));
+    }
+
+    #[test]
+    fn dmidecode() {
+        let bundle = TempDir::new("sosreport-dmidecode");
+        bundle.write("sys/class/dmi/id/product_name", "PRODUCT\n");
+        bundle.write("sos_commands/hardware/dmidecode", "\
+Handle 0x0002, DMI type 2, 15 bytes
+Base Board Information
+\tManufacturer: VENDOR
+\tProduct Name: NAME
+");
+        let (board, file) = BoardId::from_sosreport(bundle.path()).unwrap();
+        assert_eq!(board, BoardId::from_parts(Some(b"VENDOR"), Some(b"NAME"), None).unwrap());
+        assert_eq!(file, Path::new("sos_commands/hardware/dmidecode"));
+    }
+
+    #[test]
+    fn not_found() {
+        let bundle = TempDir::new("sosreport-empty");
+        assert_eq!(BoardId::from_sosreport(bundle.path()).unwrap_err().kind(), io::ErrorKind::NotFound);
+        bundle.write("sys/class/dmi/id/product_name", "PRODUCT\n");
+        bundle.write("sos_commands/hardware/dmidecode", "# dmidecode 3.3\n");
+        assert_eq!(BoardId::from_sosreport(bundle.path()).unwrap_err().kind(), io::ErrorKind::NotFound);
+        assert_eq!(BoardId::from_sosreport(&bundle.path().join("missing")).unwrap_err().kind(), io::ErrorKind::NotFound);
+        let tarball = bundle.write("sosreport.tar.xz", "");
+        assert_eq!(BoardId::from_sosreport(&tarball).unwrap_err().kind(), io::ErrorKind::Unsupported);
+    }
+}
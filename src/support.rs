@@ -0,0 +1,63 @@
+//! [`Support`], a runtime query for whether detection can work.
+
+use std::path::Path;
+
+use crate::scan::SOURCES;
+
+/// Whether detection can work on the system.
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub enum Support {
+    /// Detection has a data source to read.
+    Supported,
+    /// The platform has a detection backend, but the system has none of its data sources (neither
+    /// DMI nor a device tree), so detection will find nothing.
+    NoDmi,
+    /// The platform has no detection backend (only Linux does), so detection will find nothing.
+    UnsupportedPlatform,
+}
+
+/// Checks whether detection can work on the system without attempting it.
+///
+/// Only cheap existence checks are done, so a [`Support::Supported`] detection may still fail or
+/// find nothing. See [`supported_at`].
+pub fn supported() -> Support { supported_at("/") }
+
+/// Checks whether detection can work on the system whose filesystem is rooted at the given path,
+/// without attempting it.
+///
+/// See [`supported`].
+pub fn supported_at(root: impl AsRef<Path>) -> Support { support(cfg!(target_os = "linux"), root.as_ref()) }
+
+/// The logic of [`supported_at`], given whether the platform has a detection backend.
+fn support(backend: bool, root: &Path) -> Support {
+    if !backend { return Support::UnsupportedPlatform }
+    if SOURCES.iter().any(|source| root.join(source.dir).is_dir()) { Support::Supported } else { Support::NoDmi }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    #[test]
+    fn supported() {
+        let dmi = TempDir::new("support-dmi");
+        dmi.write("sys/class/dmi/id/board_name", "NAME\n");
+        assert_eq!(support(true, dmi.path()), Support::Supported);
+        let device_tree = TempDir::new("support-device-tree");
+        device_tree.write("sys/firmware/devicetree/base/model", "MODEL\0");
+        assert_eq!(support(true, device_tree.path()), Support::Supported);
+    }
+
+    #[test]
+    fn no_dmi() {
+        assert_eq!(supported_at(TempDir::new("support-empty").path()), Support::NoDmi);
+    }
+
+    #[test]
+    fn unsupported_platform() {
+        let dmi = TempDir::new("support-unsupported");
+        dmi.write("sys/class/dmi/id/board_name", "NAME\n");
+        assert_eq!(support(false, dmi.path()), Support::UnsupportedPlatform);
+    }
+}
@@ -0,0 +1,87 @@
+//! Discovering where sysfs is mounted, for systems that don't mount it at `/sys`.
+
+use std::{io::{self, BufRead, Read}, path::{Path, PathBuf}, str};
+
+/// The mount table, relative to the filesystem root.
+pub(crate) const MOUNTS: &str = "proc/self/mounts";
+
+/// The most bytes of the mount table that are scanned.
+const MAX_MOUNTS_LEN: u64 = 1 << 20;
+
+/// Decodes the octal escapes (e.g. `\040` for a space) the kernel uses in mount table fields.
+fn unescape(field: &str) -> String {
+    let mut out = String::with_capacity(field.len());
+    let mut rest = field;
+    while let Some(i) = rest.find('\\') {
+        out.push_str(&rest[..i]);
+        let code = rest.get(i + 1..i + 4).and_then(|code| u8::from_str_radix(code, 8).ok());
+        match code {
+            Some(code) => { out.push(code as char); rest = &rest[i + 4..] }
+            None => { out.push('\\'); rest = &rest[i + 1..] }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Finds the sysfs mountpoint in the mount table (in `/proc/self/mounts` format), reading at most
+/// [`MAX_MOUNTS_LEN`] bytes of it.
+///
+/// Prefers `/sys`, and otherwise takes the first sysfs mount. Returns `Ok(None)` if there's none.
+/// Lines that aren't UTF-8 (e.g. of other mounts, or cut by the limit) are skipped.
+pub(crate) fn find_mountpoint(mounts: impl Read) -> io::Result<Option<PathBuf>> {
+    let mut found = None;
+    for line in io::BufReader::new(mounts.take(MAX_MOUNTS_LEN)).split(b'\n') {
+        let line = line?;
+        let Ok(line) = str::from_utf8(&line) else { continue };
+        let mut fields = line.split(' ');
+        let (Some(_), Some(mountpoint), Some("sysfs")) = (fields.next(), fields.next(), fields.next()) else { continue };
+        if mountpoint == "/sys" { return Ok(Some(PathBuf::from(mountpoint))) }
+        found.get_or_insert_with(|| PathBuf::from(unescape(mountpoint)));
+    }
+    Ok(found)
+}
+
+/// Joins the absolute path under the filesystem root, e.g. `/mnt/sysfs` under `/target` is
+/// `/target/mnt/sysfs`.
+pub(crate) fn under_root(root: &Path, path: &Path) -> PathBuf {
+    root.join(path.strip_prefix("/").unwrap_or(path))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn mountpoint() {
+        let mounts = "proc /proc proc rw,nosuid 0 0\nsysfs /run/my\\040sys sysfs rw 0 0\nsysfs /other sysfs rw 0 0\n";
+        assert_eq!(find_mountpoint(mounts.as_bytes()).unwrap(), Some(PathBuf::from("/run/my sys")));
+        assert_eq!(find_mountpoint("proc /proc proc rw 0 0\n".as_bytes()).unwrap(), None);
+        assert_eq!(find_mountpoint("".as_bytes()).unwrap(), None);
+    }
+
+    #[test]
+    fn prefers_sys() {
+        let mounts = "sysfs /run/sysfs sysfs rw 0 0\nsysfs /sys sysfs rw 0 0\n";
+        assert_eq!(find_mountpoint(mounts.as_bytes()).unwrap(), Some(PathBuf::from("/sys")));
+    }
+
+    #[test]
+    fn bounded() {
+        let mut mounts = "tmpfs /tmp tmpfs rw 0 0\n".repeat(MAX_MOUNTS_LEN as usize / 24 + 1);
+        mounts.push_str("sysfs /run/sysfs sysfs rw 0 0\n");
+        assert_eq!(find_mountpoint(mounts.as_bytes()).unwrap(), None);
+    }
+
+    #[test]
+    fn not_utf8() {
+        let mounts = b"tmpfs /mnt/\xff\xfe tmpfs rw 0 0\nsysfs /run/sysfs sysfs rw 0 0\nsysfs /\xff sysfs rw 0 0";
+        assert_eq!(find_mountpoint(&mounts[..]).unwrap(), Some(PathBuf::from("/run/sysfs")));
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(unescape(r"/a\040b\134c"), r"/a b\c");
+        assert_eq!(unescape(r"/a\b"), r"/a\b");
+    }
+}
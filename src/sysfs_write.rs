@@ -0,0 +1,68 @@
+//! Writing [`BoardId`]s in the sysfs DMI format, e.g. for fixtures and emulated sysfs trees.
+
+use std::{fs, io::{self, Write}, path::Path};
+
+use crate::{BoardId, DmiField, Part};
+
+impl BoardId {
+    /// Writes the part in the format of its sysfs DMI attribute file: the part followed by LF, or
+    /// nothing if it's undetected.
+    pub fn write_part(&self, mut w: impl Write, part: Part) -> io::Result<()> {
+        let Some(value) = self.part(part) else { return Ok(()) };
+        w.write_all(value)?;
+        w.write_all(b"\n")
+    }
+
+    /// Writes the [`BoardId`] into the directory as the `board_vendor`, `board_name` and
+    /// `board_version` files of sysfs (see [`Self::write_part`]), creating the directory if needed.
+    ///
+    /// The files of undetected parts are removed if they exist, so detection from the directory
+    /// (with the directory as e.g. `sys/class/dmi/id` under a root given to [`Self::detect_at`])
+    /// gets the same [`BoardId`], except for parts ending with CR or LF, which detection strips.
+    pub fn write_to_dir(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        for (part, field) in Part::ALL.into_iter().zip([DmiField::BoardVendor, DmiField::BoardName, DmiField::BoardVersion]) {
+            let path = dir.join(field.attribute());
+            if self.part(part).is_some() {
+                let mut contents = Vec::new();
+                self.write_part(&mut contents, part)?;
+                fs::write(path, contents)?;
+            } else {
+                match fs::remove_file(path) {
+                    Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    #[test]
+    fn write_part() {
+        let board = BoardId::from_fixture("raspberry_pi_4").unwrap();
+        let mut out = Vec::new();
+        board.write_part(&mut out, Part::Vendor).unwrap();
+        board.write_part(&mut out, Part::Version).unwrap();
+        assert_eq!(out, b"Raspberry Pi\n");
+    }
+
+    #[test]
+    fn round_trip() {
+        let root = TempDir::new("sysfs-write-round-trip");
+        let dir = root.path().join("sys/class/dmi/id");
+        for name in BoardId::fixture_names() {
+            let board = BoardId::from_fixture(name).unwrap();
+            board.write_to_dir(&dir).unwrap();
+            let detected = BoardId::detect_at(root.path()).unwrap();
+            assert_eq!(detected, board, "{name}");
+        }
+        assert!(!dir.join("board_version").exists(), "stale file left behind");
+    }
+}
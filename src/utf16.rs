@@ -0,0 +1,91 @@
+//! Ingesting UTF-16LE encoded parts, e.g. from Windows-exported dumps.
+
+use std::io::{self, Read};
+
+use crate::{BoardId, CapacityError, NewlinePolicy, Part};
+
+impl BoardId {
+    /// Attempts to make a [`BoardId`] from UTF-16LE encoded streams, e.g. from a
+    /// Windows-exported identity dump.
+    ///
+    /// Each part is decoded to UTF-8, without a leading byte order mark, and then trimmed as per
+    /// the default [`NewlinePolicy`].
+    /// Fails with [`io::ErrorKind::InvalidData`] if a part isn't valid UTF-16LE, or with
+    /// [`io::ErrorKind::WriteZero`] if the decoded parts don't fit in the buffer.
+    pub fn from_streams_utf16le(vendor: Option<impl Read>, name: Option<impl Read>, version: Option<impl Read>) -> io::Result<Self> {
+        /// Reads and decodes a part, which comes after `used` bytes of other parts.
+        fn read(part: Part, stream: Option<impl Read>, used: usize) -> io::Result<Vec<u8>> {
+            let Some(stream) = stream else { return Ok(Vec::new()) };
+            let mut bytes = Vec::new();
+            // Every code unit decodes to at least one byte, so reading one unit more than could fit
+            // alongside a BOM and a CRLF is enough to tell the part doesn't fit.
+            let limit = 2 * (BoardId::BUFSZ as u64 + 4);
+            stream.take(limit).read_to_end(&mut bytes)?;
+            // the largest part that fits is a BOM, 255 units and a CRLF, one unit short of the limit
+            if bytes.len() as u64 == limit {
+                return Err(CapacityError { part, attempted: bytes.len() / 2, remaining: BoardId::BUFSZ - used }.into())
+            }
+            let (units, rest) = bytes.as_chunks::<2>();
+            if !rest.is_empty() { return Err(io::Error::new(io::ErrorKind::InvalidData, "the UTF-16 part has an odd number of bytes")) }
+            let mut decoded = char::decode_utf16(units.iter().map(|&unit| u16::from_le_bytes(unit)))
+                .collect::<Result<String, _>>()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+                .into_bytes();
+            if decoded.starts_with("\u{FEFF}".as_bytes()) { decoded.drain(..3); }
+            decoded.truncate(NewlinePolicy::default().trimmed_len(&decoded));
+            Ok(decoded)
+        }
+
+        let vendor = read(Part::Vendor, vendor, 0)?;
+        let name = read(Part::Name, name, vendor.len().min(BoardId::BUFSZ))?;
+        let version = read(Part::Version, version, (vendor.len() + name.len()).min(BoardId::BUFSZ))?;
+        Ok(Self::from_parts(Some(&vendor), Some(&name), Some(&version))?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const NOENT: Option<&[u8]> = None;
+
+    fn utf16le(s: &str) -> Vec<u8> { s.encode_utf16().flat_map(u16::to_le_bytes).collect() }
+
+    #[test]
+    fn name() {
+        let name = utf16le("NAME");
+        let board = BoardId::from_streams_utf16le(NOENT, Some(name.as_slice()), NOENT).unwrap();
+        assert_eq!( board.vendor(), None);
+        assert_eq!(   board.name(), Some("NAME".as_bytes()));
+        assert_eq!(board.version(), None);
+    }
+
+    #[test]
+    fn bom_and_crlf() {
+        let [vendor, name] = ["\u{FEFF}VENDOR\r\n", "NÄME\r\n"].map(utf16le);
+        let board = BoardId::from_streams_utf16le(Some(vendor.as_slice()), Some(name.as_slice()), NOENT).unwrap();
+        assert_eq!(board.vendor(), Some("VENDOR".as_bytes()));
+        assert_eq!(  board.name(), Some("NÄME"  .as_bytes()));
+    }
+
+    #[test]
+    fn invalid() {
+        let odd = b"N\0A".as_slice();
+        assert_eq!(BoardId::from_streams_utf16le(NOENT, Some(odd), NOENT).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        let lone_surrogate = b"\x00\xd8".as_slice();
+        assert_eq!(BoardId::from_streams_utf16le(NOENT, Some(lone_surrogate), NOENT).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn too_large() {
+        let name = utf16le(&"N".repeat(BoardId::BUFSZ + 1));
+        assert_eq!(BoardId::from_streams_utf16le(NOENT, Some(name.as_slice()), NOENT).unwrap_err().kind(), io::ErrorKind::WriteZero);
+        // reaching the limit of 259 code units fails even when it cuts a surrogate pair
+        let name = utf16le(&format!("N{}", "😀".repeat(200)));
+        assert_eq!(BoardId::from_streams_utf16le(NOENT, Some(name.as_slice()), NOENT).unwrap_err().kind(), io::ErrorKind::WriteZero);
+        let name = utf16le(&format!("\u{FEFF}{}\r\n{}", "N".repeat(BoardId::BUFSZ), "😀".repeat(10)));
+        assert_eq!(BoardId::from_streams_utf16le(NOENT, Some(name.as_slice()), NOENT).unwrap_err().kind(), io::ErrorKind::WriteZero);
+        let name = utf16le(&format!("\u{FEFF}{}\r\n", "N".repeat(BoardId::BUFSZ)));
+        assert_eq!(BoardId::from_streams_utf16le(NOENT, Some(name.as_slice()), NOENT).unwrap().name().unwrap().len(), BoardId::BUFSZ, "the largest part must fit");
+    }
+}
@@ -0,0 +1,75 @@
+//! [`BoardId::vendor_short`], the vendor without its legal-entity noise.
+
+use alloc::{string::String, vec::Vec};
+
+use crate::BoardId;
+
+/// The corporate suffixes [`BoardId::vendor_short`] strips, compared case-insensitively, without
+/// trailing punctuation (which is stripped separately).
+pub const CORPORATE_SUFFIXES: &[&str] = &[
+    "Co., Ltd", "Co. Ltd", "Co.,Ltd", "Co", "Ltd", "Limited",
+    "Inc", "Corporation", "Corp", "LLC", "GmbH", "S.A", "B.V",
+    "Computer", "Technology", "Technologies", "International",
+];
+
+/// Trailing punctuation (and whitespace) [`BoardId::vendor_short`] strips.
+const PUNCTUATION: &[char] = &[',', '.', ';', ' '];
+
+/// Strips one corporate suffix off the vendor (which has no trailing punctuation), if it ends
+/// with one as a whole word.
+fn strip_suffix(vendor: &str) -> Option<&str> {
+    CORPORATE_SUFFIXES.iter().find_map(|suffix| {
+        let start = vendor.len().checked_sub(suffix.len())?;
+        let rest = vendor.as_bytes();
+        let whole_word = start == 0 || matches!(rest[start - 1], b' ' | b',');
+        (whole_word && rest[start..].eq_ignore_ascii_case(suffix.as_bytes())).then(|| &vendor[..start])
+    })
+}
+
+impl BoardId {
+    /// Gets the vendor without its legal-entity noise, e.g. "ASUSTeK" for "ASUSTeK COMPUTER INC.",
+    /// or `None` if it's undetected.
+    ///
+    /// Whitespace runs are collapsed into a space, then [corporate suffixes](CORPORATE_SUFFIXES)
+    /// and trailing punctuation are repeatedly stripped. A suffix that's all that's left is kept,
+    /// so the vendor is never emptied (unless it's blank).
+    pub fn vendor_short(&self) -> Option<String> {
+        let vendor = String::from_utf8_lossy(self.vendor()?).split_whitespace().collect::<Vec<_>>().join(" ");
+        let mut short = vendor.trim_end_matches(PUNCTUATION);
+        while let Some(stripped) = strip_suffix(short).map(|stripped| stripped.trim_end_matches(PUNCTUATION)) {
+            if stripped.is_empty() { break }
+            short = stripped;
+        }
+        Some(String::from(if short.is_empty() { &vendor } else { short }))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn short() {
+        for (vendor, short) in [
+            ("ASUSTeK COMPUTER INC.", "ASUSTeK"),
+            ("Micro-Star International Co., Ltd.", "Micro-Star"),
+            ("GIGA-BYTE TECHNOLOGY CO., LTD.", "GIGA-BYTE"),
+            ("Gigabyte Technology Co., Ltd.", "Gigabyte"),
+            ("ASRock Incorporation", "ASRock Incorporation"),
+            ("Dell Inc.", "Dell"),
+            ("Intel Corporation", "Intel"),
+            ("American Megatrends International, LLC.", "American Megatrends"),
+            ("Apple  Inc.", "Apple"),
+            ("LENOVO", "LENOVO"),
+            ("Hewlett-Packard", "Hewlett-Packard"),
+            ("Supermicro", "Supermicro"),
+            ("Technology Inc.", "Technology"),
+            ("Inc.", "Inc"),
+            ("Cisco", "Cisco"),
+        ] {
+            let board = BoardId::from_parts(Some(vendor.as_bytes()), None, None).unwrap();
+            assert_eq!(board.vendor_short().as_deref(), Some(short), "{vendor:?}");
+        }
+        assert_eq!(BoardId::from_parts(None, Some(b"NAME"), None).unwrap().vendor_short(), None);
+    }
+}
@@ -0,0 +1,104 @@
+//! [`BoardId::watch`], a stream of the [`BoardId`]'s changes.
+
+use std::{io, iter, path::Path, thread, time::Duration};
+
+use crate::{BoardId, Part, PartChange};
+
+/// A change of the [`BoardId`], yielded by [`BoardId::watch`].
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub struct BoardIdChange {
+    /// The board before the change, or `None` for the initial identity.
+    pub old: Option<BoardId>,
+    /// The board after the change.
+    pub new: BoardId,
+}
+
+impl BoardIdChange {
+    /// Gets the parts that changed, in vendor, name, version order.
+    ///
+    /// For the initial identity, it's every detected part.
+    pub fn changes(&self) -> Vec<PartChange> {
+        match &self.old {
+            Some(old) => PartChange::between(old, &self.new),
+            None => Part::ALL.into_iter()
+                .filter_map(|part| Some(PartChange { part, old: None, new: Some(self.new.part(part)?.to_vec()) }))
+                .collect(),
+        }
+    }
+}
+
+impl BoardId {
+    /// Polls detection every interval, yielding the initial identity, then only a change of any
+    /// part, e.g. of a live-migrated VM.
+    ///
+    /// See [`Self::watch_at`].
+    pub fn watch(interval: Duration) -> impl Iterator<Item = io::Result<BoardIdChange>> { Self::watch_at("/", interval) }
+
+    /// Polls detection of the system whose filesystem is rooted at the given path every interval,
+    /// yielding the initial identity, then only a change of any part.
+    ///
+    /// The first poll is immediate, and the iterator blocks until the next change. It never ends;
+    /// failed polls are yielded as errors, and the next change is relative to the last board
+    /// detected.
+    pub fn watch_at(root: impl AsRef<Path>, interval: Duration) -> impl Iterator<Item = io::Result<BoardIdChange>> {
+        let root = root.as_ref().to_owned();
+        watch_with(interval, move || Self::detect_at(&root))
+    }
+}
+
+/// The logic of [`BoardId::watch_at`], detecting with the given function.
+fn watch_with(interval: Duration, mut detect: impl FnMut() -> io::Result<BoardId>) -> impl Iterator<Item = io::Result<BoardIdChange>> {
+    let (mut last, mut polled) = (None::<BoardId>, false);
+    iter::from_fn(move || loop {
+        if polled { thread::sleep(interval) }
+        polled = true;
+        let board = match detect() { Ok(board) => board, Err(e) => return Some(Err(e)) };
+        if last.is_some_and(|last| PartChange::between(&last, &board).is_empty()) { continue }
+        return Some(Ok(BoardIdChange { old: last.replace(board), new: board }))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    fn board(name: &[u8]) -> BoardId { BoardId::from_parts(Some(b"VENDOR"), Some(name), None).unwrap() }
+
+    #[test]
+    fn one_change() {
+        let boards = [board(b"A"), board(b"A"), board(b"A"), board(b"B")];
+        let mut polls = 0;
+        let mut changes = watch_with(Duration::ZERO, || {
+            polls += 1;
+            Ok(boards[(polls - 1).min(boards.len() - 1)])
+        });
+        assert_eq!(changes.next().unwrap().unwrap(), BoardIdChange { old: None, new: board(b"A") });
+        let change = changes.next().unwrap().unwrap();
+        assert_eq!(change, BoardIdChange { old: Some(board(b"A")), new: board(b"B") });
+        assert_eq!(change.changes(), [PartChange { part: Part::Name, old: Some(b"A".to_vec()), new: Some(b"B".to_vec()) }]);
+        drop(changes);
+        assert_eq!(polls, 4);
+    }
+
+    #[test]
+    fn errors() {
+        let mut results = [Ok(board(b"A")), Err(io::ErrorKind::PermissionDenied.into()), Ok(board(b"A")), Ok(board(b"B"))].into_iter();
+        let mut changes = watch_with(Duration::ZERO, move || results.next().unwrap());
+        assert!(changes.next().unwrap().is_ok());
+        assert_eq!(changes.next().unwrap().unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+        assert_eq!(changes.next().unwrap().unwrap().old, Some(board(b"A")), "the last board was forgotten");
+    }
+
+    #[test]
+    fn at_root() {
+        let root = TempDir::new("watch");
+        root.write("sys/class/dmi/id/board_vendor", "VENDOR\n");
+        root.write("sys/class/dmi/id/board_name", "A\n");
+        let mut changes = BoardId::watch_at(root.path(), Duration::from_millis(1));
+        let initial = changes.next().unwrap().unwrap();
+        assert_eq!(initial.changes().len(), 2);
+        root.write("sys/class/dmi/id/board_name", "B\n");
+        assert_eq!(changes.next().unwrap().unwrap().new, board(b"B"));
+    }
+}
@@ -0,0 +1,42 @@
+//! [`BoardId::collapse_whitespace`], normalizing whitespace runs inside parts.
+
+use crate::BoardId;
+
+impl BoardId {
+    /// Makes a [`BoardId`] with every run of ASCII whitespace in each part replaced by a single
+    /// space, e.g. "PRIME   Z790" becomes "PRIME Z790".
+    ///
+    /// Leading and trailing runs are collapsed too, not trimmed.
+    pub fn collapse_whitespace(&self) -> BoardId {
+        Self::repack(|part| {
+            let mut in_run = false;
+            self.part(part).unwrap_or_default().iter().filter_map(move |&b| {
+                let space = b.is_ascii_whitespace();
+                let keep = !(space && in_run);
+                in_run = space;
+                keep.then_some(if space { b' ' } else { b })
+            })
+        })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn collapses() {
+        let board = BoardId::from_parts(Some(b"ASUSTeK  COMPUTER\tINC."), Some(b"PRIME   Z790"), Some(b"Rev\r\n1.xx")).unwrap();
+        assert_eq!(board.collapse_whitespace(), BoardId::from_parts(Some(b"ASUSTeK COMPUTER INC."), Some(b"PRIME Z790"), Some(b"Rev 1.xx")).unwrap());
+    }
+
+    #[test]
+    fn edges() {
+        let board = BoardId::from_parts(Some(b"  A  "), None, Some(b"B")).unwrap();
+        assert_eq!(board.collapse_whitespace(), BoardId::from_parts(Some(b" A "), None, Some(b"B")).unwrap());
+        let board = BoardId::from_parts(Some(b"A"), Some(b"NAME"), None).unwrap();
+        assert_eq!(board.collapse_whitespace(), board);
+        let full = BoardId::from_parts(Some(&[b' '; 255]), None, None).unwrap();
+        assert_eq!(full.collapse_whitespace().vendor(), Some(&b" "[..]));
+    }
+}
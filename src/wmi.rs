@@ -0,0 +1,90 @@
+//! Parsing [`BoardId`]s out of Windows WMI text exports of `Win32_BaseBoard`.
+
+#[cfg(feature = "std")] use std::io;
+
+use crate::{BoardId, CapacityError};
+
+impl BoardId {
+    /// Parses the [`BoardId`] out of a WMI text export of `Win32_BaseBoard`, from its
+    /// Manufacturer, Product and Version properties.
+    ///
+    /// Both the `Key=Value` layout of `wmic baseboard get /format:list` and the `Key : Value`
+    /// layout of PowerShell's `Get-CimInstance Win32_BaseBoard | Format-List` are handled. Other
+    /// properties are ignored, and the first occurrence of each property wins. Returns `Ok(None)`
+    /// if none of the properties are present.
+    pub fn from_wmi_text(text: &str) -> Result<Option<Self>, CapacityError> {
+        let mut parts = [None; 3];
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once(['=', ':']) else { continue };
+            let index = match key.trim() {
+                key if key.eq_ignore_ascii_case("Manufacturer") => 0,
+                key if key.eq_ignore_ascii_case("Product")      => 1,
+                key if key.eq_ignore_ascii_case("Version")      => 2,
+                _ => continue,
+            };
+            parts[index].get_or_insert(value.trim());
+        }
+        if parts.iter().all(Option::is_none) { return Ok(None) }
+        let [vendor, name, version] = parts.map(|part| part.filter(|part| !part.is_empty()).map(str::as_bytes));
+        Self::from_parts(vendor, name, version).map(Some)
+    }
+
+    /// Parses the [`BoardId`] out of a WMI text export's bytes, which are UTF-16LE if they start
+    /// with its byte order mark (as `wmic` writes them by default), and UTF-8 otherwise.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`] if the bytes aren't valid in their encoding. See
+    /// [`Self::from_wmi_text`].
+    #[cfg(feature = "std")]
+    pub fn from_wmi_bytes(bytes: &[u8]) -> io::Result<Option<Self>> {
+        let invalid = |e: Box<dyn std::error::Error + Send + Sync>| io::Error::new(io::ErrorKind::InvalidData, e);
+        let text = match bytes.strip_prefix(&[0xFF, 0xFE]) {
+            Some(utf16) => {
+                let (units, rest) = utf16.as_chunks::<2>();
+                if !rest.is_empty() { return Err(invalid("the UTF-16 export has an odd number of bytes".into())) }
+                char::decode_utf16(units.iter().map(|&unit| u16::from_le_bytes(unit)))
+                    .collect::<Result<String, _>>()
+                    .map_err(|e| invalid(e.into()))?
+            }
+            None => String::from_utf8(bytes.strip_prefix("\u{FEFF}".as_bytes()).unwrap_or(bytes).to_vec()).map_err(|e| invalid(e.into()))?,
+        };
+        Ok(Self::from_wmi_text(&text)?)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    /// Output of `wmic baseboard get /format:list`.
+    const WMIC: &str = "\r\n\r\nCaption=Base Board\r\nManufacturer=ASUSTeK COMPUTER INC.\r\nModel=\r\nName=Base Board\r\nProduct=PRIME B550-PLUS\r\nSerialNumber=201075725201234\r\nVersion=Rev X.0x\r\n\r\n\r\n";
+
+    /// Output of `Get-CimInstance Win32_BaseBoard | Format-List`.
+    const POWERSHELL: &str = "\r\nManufacturer : Dell Inc.\r\nModel        :\r\nName         : Base Board\r\nSerialNumber : /ABC1234/CN1296398A0123/\r\nSKU          :\r\nProduct      : 0XR032\r\nVersion      :\r\n\r\n";
+
+    fn board(vendor: &[u8], name: &[u8], version: Option<&[u8]>) -> Option<BoardId> {
+        Some(BoardId::from_parts(Some(vendor), Some(name), version).unwrap())
+    }
+
+    #[test]
+    fn wmic() {
+        assert_eq!(BoardId::from_wmi_text(WMIC).unwrap(), board(b"ASUSTeK COMPUTER INC.", b"PRIME B550-PLUS", Some(b"Rev X.0x")));
+    }
+
+    #[test]
+    fn powershell() {
+        assert_eq!(BoardId::from_wmi_text(POWERSHELL).unwrap(), board(b"Dell Inc.", b"0XR032", None));
+    }
+
+    #[test]
+    fn utf16le() {
+        let bytes = [0xFF, 0xFE].into_iter().chain(WMIC.encode_utf16().flat_map(u16::to_le_bytes)).collect::<Vec<_>>();
+        assert_eq!(BoardId::from_wmi_bytes(&bytes).unwrap(), BoardId::from_wmi_text(WMIC).unwrap());
+        assert_eq!(BoardId::from_wmi_bytes(POWERSHELL.as_bytes()).unwrap(), BoardId::from_wmi_text(POWERSHELL).unwrap());
+        assert_eq!(BoardId::from_wmi_bytes(&bytes[..bytes.len() - 1]).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(BoardId::from_wmi_text("Caption=Base Board\r\n"), Ok(None));
+    }
+}